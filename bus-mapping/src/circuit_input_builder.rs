@@ -2,6 +2,7 @@
 //! types from geth / web3 and outputs the circuit inputs.
 
 mod access;
+mod analysis;
 mod block;
 mod call;
 mod execution;
@@ -22,7 +23,8 @@ use crate::{
     util::{hash_code_keccak, KECCAK_CODE_HASH_EMPTY},
 };
 pub use access::{Access, AccessSet, AccessValue, CodeSource};
-pub use block::{Block, BlockContext};
+pub use analysis::{find_unsupported_features, UnsupportedFeature};
+pub use block::{Block, BlockContext, BlockRowUsage};
 pub use call::{Call, CallContext, CallKind};
 use core::fmt::Debug;
 use eth_types::{
@@ -79,6 +81,23 @@ impl Default for PrecompileEcParams {
     }
 }
 
+/// Default rows per round of the keccak circuit, see [`keccak_rows_per_round`].
+pub const DEFAULT_KECCAK_ROWS: usize = 12;
+
+/// Rows per round of the keccak circuit, set by the `KECCAK_ROWS` env var.
+pub fn keccak_rows_per_round() -> usize {
+    std::env::var("KECCAK_ROWS")
+        .unwrap_or_else(|_| format!("{DEFAULT_KECCAK_ROWS}"))
+        .parse()
+        .expect("Cannot parse KECCAK_ROWS env var as usize")
+}
+
+/// Rows of one keccak-f permutation in the keccak circuit: its 24 rounds and the
+/// absorb round.
+pub fn keccak_rows_per_permutation() -> usize {
+    keccak_rows_per_round() * 25
+}
+
 /// Circuit Setup Parameters
 #[derive(Debug, Clone, Copy)]
 pub struct CircuitsParams {
@@ -249,7 +268,19 @@ impl<'a> CircuitInputBuilder {
             ),
         );
 
-        Transaction::new(call_id, &self.sdb, &mut self.code_db, eth_tx, is_success)
+        let mut tx = Transaction::new(call_id, &self.sdb, &mut self.code_db, eth_tx, is_success)?;
+        // The gas price of a dynamic fee tx is its effective gas price, which is not set for
+        // pending (and mock) txs.
+        if tx.tx_type.is_eip1559() {
+            let base_fee = self
+                .block
+                .headers
+                .get(&tx.block_num)
+                .map(|header| header.base_fee)
+                .unwrap_or_default();
+            tx.gas_price = tx.gas_fee_cap.min(base_fee + tx.gas_tip_cap);
+        }
+        Ok(tx)
     }
 
     /// Iterate over all generated CallContext RwCounterEndOfReversion
@@ -291,6 +322,16 @@ impl<'a> CircuitInputBuilder {
         handle_rwc_reversion: bool,
         check_last_tx: bool,
     ) -> Result<(), Error> {
+        // Withdrawals (EIP-4895) credit balances outside of any transaction, which is not
+        // supported by the circuits.
+        if eth_block
+            .withdrawals
+            .as_ref()
+            .is_some_and(|withdrawals| !withdrawals.is_empty())
+        {
+            return Err(Error::InternalError("block withdrawals are not supported"));
+        }
+
         // accumulates gas across all txs in the block
         log::info!(
             "handling block {:?}, tx num {}",
@@ -338,6 +379,7 @@ impl<'a> CircuitInputBuilder {
                 geth_trace,
                 check_last_tx && tx_index + 1 == eth_block.transactions.len(),
             )?;
+            self.check_capacity()?;
             log::debug!(
                 "after handle {}th tx: rwc {:?}, total gas {:?}",
                 chunk_tx_idx,
@@ -522,7 +564,11 @@ impl<'a> CircuitInputBuilder {
                     total_rws,
                     max_rws
                 );
-                return Err(Error::InternalError("rws not enough"));
+                return Err(Error::CapacityExceeded {
+                    circuit: "state",
+                    used: total_rws + 1,
+                    limit: max_rws,
+                });
             };
         }
         push_op(&mut end_block_last, RWCounter(1), RW::READ, StartOp {});
@@ -540,6 +586,38 @@ impl<'a> CircuitInputBuilder {
         Ok(())
     }
 
+    /// Fail early, before any circuit is assigned, when the operations gathered so far already
+    /// need more rows than configured in the circuits params. A limit of 0 means the circuit
+    /// is sized to its witness and is not checked. Rws are checked as they are pushed, see
+    /// [`CircuitInputStateRef::check_rw_num_limit`].
+    ///
+    /// The usage is tracked as the events are pushed, so checking after each tx doesn't rescan
+    /// the block.
+    fn check_capacity(&mut self) -> Result<(), Error> {
+        let keccak_permutations = self.block.update_keccak_usage();
+        let params = &self.block.circuits_params;
+        for (circuit, used, limit) in [
+            // each copied byte takes a read and a write row
+            ("copy", self.block.copy_bytes() * 2, params.max_copy_rows),
+            ("exp", self.block.exp_steps(), params.max_exp_steps),
+            (
+                "keccak",
+                keccak_permutations * keccak_rows_per_permutation(),
+                params.max_keccak_rows,
+            ),
+        ] {
+            if limit != 0 && used > limit {
+                log::error!("{circuit} circuit capacity exceeded: {used} > {limit}");
+                return Err(Error::CapacityExceeded {
+                    circuit,
+                    used,
+                    limit,
+                });
+            }
+        }
+        Ok(())
+    }
+
     /// Handle a transaction with its corresponding execution trace to generate
     /// all the associated operations.  Each operation is registered in
     /// `self.block.container`, and each step stores the
@@ -574,6 +652,22 @@ impl<'a> CircuitInputBuilder {
         debug_tx.rlp_unsigned_bytes.clear();
         log::trace!("handle_tx tx {:?}", debug_tx);
 
+        // A tx failing the pre-execution checks is not executed at all, only
+        // its failed receipt is written.
+        if geth_trace.invalid {
+            let invalid_tx_steps = gen_associated_steps(
+                &mut self.state_ref(&mut tx, &mut tx_ctx),
+                ExecState::InvalidTx,
+            )?;
+            tx.steps_mut().extend(invalid_tx_steps);
+
+            self.sdb.commit_tx();
+            self.block.txs.push(tx);
+            log::trace!("handle_tx finished, tx is invalid");
+
+            return Ok(());
+        }
+
         // Generate BeginTx step
         let begin_tx_steps = gen_associated_steps(
             &mut self.state_ref(&mut tx, &mut tx_ctx),
@@ -747,9 +841,9 @@ pub fn keccak_inputs(block: &Block, code_db: &CodeDB) -> Result<Vec<Vec<u8>>, Er
         &block.headers,
         block.txs(),
     ));
-    // Bytecode Circuit
-    for _bytecode in code_db.0.values() {
-        // keccak_inputs.push(bytecode.clone());
+    // Bytecode Circuit, which only hashes bytecodes with keccak in the dual code hash mode
+    if cfg!(feature = "dual-codehash") {
+        keccak_inputs.extend(code_db.0.values().cloned());
     }
     log::debug!(
         "keccak total len after bytecodes: {}",
@@ -1047,6 +1141,19 @@ impl<P: JsonRpcClient> BuilderClient<P> {
         ))
     }
 
+    /// Fetch block `block_num` and its traces, and report the features it uses
+    /// which the circuits can not prove, without building any witness.
+    pub async fn find_unsupported_features(
+        &self,
+        block_num: u64,
+    ) -> Result<Vec<(usize, UnsupportedFeature)>, Error> {
+        let (eth_block, geth_traces, _, _) = self.get_block(block_num).await?;
+        Ok(find_unsupported_features(
+            &eth_block.transactions,
+            &geth_traces,
+        ))
+    }
+
     /// Step 2. Get State Accesses from TxExecTraces
     pub async fn get_state_accesses(&self, eth_block: &EthBlock) -> Result<AccessSet, Error> {
         let mut access_set = AccessSet::default();
@@ -1121,81 +1228,118 @@ impl<P: JsonRpcClient> BuilderClient<P> {
         ),
         Error,
     > {
-        let traces = if let Some(tx_hash) = tx_hash {
-            vec![self.cli.trace_tx_prestate_by_hash(tx_hash).await?]
-        } else {
-            self.cli
-                .trace_block_prestate_by_hash(
-                    eth_block
-                        .hash
-                        .ok_or(Error::EthTypeError(eth_types::Error::IncompleteBlock))?,
-                )
-                .await?
-        };
+        self.get_pre_state_of_blocks(std::slice::from_ref(eth_block), tx_hash)
+            .await
+    }
+
+    /// Same as [`Self::get_pre_state`] for a range of consecutive blocks, with one
+    /// prestate tracing call per block. An account or slot takes the prestate of the
+    /// first block touching it, which is its state before the range.
+    pub async fn get_pre_state_multi(
+        &self,
+        eth_blocks: &[EthBlock],
+    ) -> Result<
+        (
+            Vec<eth_types::EIP1186ProofResponse>,
+            HashMap<Address, Vec<u8>>,
+        ),
+        Error,
+    > {
+        self.get_pre_state_of_blocks(eth_blocks, None).await
+    }
+
+    async fn get_pre_state_of_blocks(
+        &self,
+        eth_blocks: &[EthBlock],
+        tx_hash: Option<H256>,
+    ) -> Result<
+        (
+            Vec<eth_types::EIP1186ProofResponse>,
+            HashMap<Address, Vec<u8>>,
+        ),
+        Error,
+    > {
+        let first_block_num = eth_blocks
+            .first()
+            .and_then(|eth_block| eth_block.number)
+            .ok_or(Error::EthTypeError(eth_types::Error::IncompleteBlock))?;
+        assert_ne!(
+            first_block_num.as_u64(),
+            0,
+            "is not expected to access genesis block"
+        );
 
         let mut account_set =
             HashMap::<Address, (eth_types::EIP1186ProofResponse, HashMap<Word, Word>)>::new();
         let mut code_set = HashMap::new();
 
-        for trace in traces.into_iter() {
-            for (addr, prestate) in trace.into_iter() {
-                let (_, storages) = account_set.entry(addr).or_insert_with(|| {
-                    let code_size =
-                        Word::from(prestate.code.as_ref().map(|bt| bt.len()).unwrap_or(0));
-                    let (code_hash, keccak_code_hash) = if let Some(bt) = prestate.code {
-                        let h = CodeDB::hash(&bt);
-                        // only require for L2
-                        let keccak_h = if cfg!(feature = "scroll") {
-                            hash_code_keccak(&bt)
+        for eth_block in eth_blocks {
+            let traces = if let Some(tx_hash) = tx_hash {
+                vec![self.cli.trace_tx_prestate_by_hash(tx_hash).await?]
+            } else {
+                self.cli
+                    .trace_block_prestate_by_hash(
+                        eth_block
+                            .hash
+                            .ok_or(Error::EthTypeError(eth_types::Error::IncompleteBlock))?,
+                    )
+                    .await?
+            };
+
+            for trace in traces.into_iter() {
+                for (addr, prestate) in trace.into_iter() {
+                    let (_, storages) = account_set.entry(addr).or_insert_with(|| {
+                        let code_size =
+                            Word::from(prestate.code.as_ref().map(|bt| bt.len()).unwrap_or(0));
+                        let (code_hash, keccak_code_hash) = if let Some(bt) = prestate.code {
+                            let h = CodeDB::hash(&bt);
+                            // only require for L2
+                            let keccak_h = if cfg!(feature = "scroll") {
+                                hash_code_keccak(&bt)
+                            } else {
+                                h
+                            };
+                            code_set.insert(addr, Vec::from(bt.as_ref()));
+                            (h, keccak_h)
                         } else {
-                            h
+                            (CodeDB::empty_code_hash(), *KECCAK_CODE_HASH_EMPTY)
                         };
-                        code_set.insert(addr, Vec::from(bt.as_ref()));
-                        (h, keccak_h)
-                    } else {
-                        (CodeDB::empty_code_hash(), *KECCAK_CODE_HASH_EMPTY)
-                    };
-
-                    (
-                        eth_types::EIP1186ProofResponse {
-                            address: addr,
-                            balance: prestate.balance.unwrap_or_default(),
-                            nonce: prestate.nonce.unwrap_or_default().into(),
-                            code_hash,
-                            keccak_code_hash,
-                            code_size,
-                            ..Default::default()
-                        },
-                        HashMap::new(),
-                    )
-                });
 
-                if let Some(stg) = prestate.storage {
-                    for (k, v) in stg {
-                        storages.entry(k).or_insert(v);
+                        (
+                            eth_types::EIP1186ProofResponse {
+                                address: addr,
+                                balance: prestate.balance.unwrap_or_default(),
+                                nonce: prestate.nonce.unwrap_or_default().into(),
+                                code_hash,
+                                keccak_code_hash,
+                                code_size,
+                                ..Default::default()
+                            },
+                            HashMap::new(),
+                        )
+                    });
+
+                    if let Some(stg) = prestate.storage {
+                        for (k, v) in stg {
+                            storages.entry(k).or_insert(v);
+                        }
                     }
                 }
             }
-        }
 
-        // a hacking? since the coinbase address is not touch in prestate
-        let coinbase_addr = eth_block
-            .author
-            .ok_or(Error::EthTypeError(eth_types::Error::IncompleteBlock))?;
-        let block_num = eth_block
-            .number
-            .ok_or(Error::EthTypeError(eth_types::Error::IncompleteBlock))?;
-        assert_ne!(
-            block_num.as_u64(),
-            0,
-            "is not expected to access genesis block"
-        );
-        if let std::collections::hash_map::Entry::Vacant(e) = account_set.entry(coinbase_addr) {
-            let coinbase_proof = self
-                .cli
-                .get_proof(coinbase_addr, Vec::new(), (block_num - 1).into())
-                .await?;
-            e.insert((coinbase_proof, HashMap::new()));
+            // a hacking? since the coinbase address is not touch in prestate.
+            // Insert it before tracing the next block, whose prestate would
+            // already include the fees credited by this one.
+            let coinbase_addr = eth_block
+                .author
+                .ok_or(Error::EthTypeError(eth_types::Error::IncompleteBlock))?;
+            if let std::collections::hash_map::Entry::Vacant(e) = account_set.entry(coinbase_addr) {
+                let coinbase_proof = self
+                    .cli
+                    .get_proof(coinbase_addr, Vec::new(), (first_block_num - 1).into())
+                    .await?;
+                e.insert((coinbase_proof, HashMap::new()));
+            }
         }
 
         Ok((
@@ -1263,6 +1407,18 @@ impl<P: JsonRpcClient> BuilderClient<P> {
         );
         for (idx, (eth_block, geth_traces)) in blocks_and_traces.iter().enumerate() {
             let is_last = idx == blocks_and_traces.len() - 1;
+            // the blocks must form a contiguous chain, as they share one state db
+            if let Some((parent, _)) = idx.checked_sub(1).map(|i| &blocks_and_traces[i]) {
+                if eth_block.number != parent.number.map(|n| n + 1u64) {
+                    return Err(Error::InternalError("blocks are not consecutive"));
+                }
+                if parent
+                    .hash
+                    .is_some_and(|hash| hash != eth_block.parent_hash)
+                {
+                    return Err(Error::InternalError("block parent hash mismatch"));
+                }
+            }
             let header = BlockHead::new(self.chain_id, Default::default(), eth_block)?;
             builder.block.headers.insert(header.number.as_u64(), header);
             builder.handle_block_inner(eth_block, geth_traces, is_last, is_last)?;
@@ -1316,14 +1472,15 @@ impl<P: JsonRpcClient> BuilderClient<P> {
         block_num_end: u64,
     ) -> Result<CircuitInputBuilder, Error> {
         let mut blocks_and_traces = Vec::new();
-        let mut access_set = AccessSet::default();
         for block_num in block_num_begin..block_num_end {
             let (eth_block, geth_traces, _, _) = self.get_block(block_num).await?;
-            let mut access_list = self.get_state_accesses(&eth_block).await?;
-            access_set.extend(&mut access_list);
             blocks_and_traces.push((eth_block, geth_traces));
         }
-        let (proofs, codes) = self.get_state(block_num_begin, access_set).await?;
+        let eth_blocks = blocks_and_traces
+            .iter()
+            .map(|(eth_block, _)| eth_block.clone())
+            .collect_vec();
+        let (proofs, codes) = self.get_pre_state_multi(&eth_blocks).await?;
         let (state_db, code_db) = Self::build_state_code_db(proofs, codes);
         let builder = self.gen_inputs_from_state_multi(state_db, code_db, &blocks_and_traces)?;
         Ok(builder)
@@ -0,0 +1,65 @@
+//! Dry-run analysis of the traces of a block, reporting what the circuits can
+//! not prove before any witness is built.
+
+use crate::precompile::{is_precompiled, PrecompileCalls};
+use eth_types::{evm_types::OpcodeId, GethExecTrace, Transaction};
+
+/// A feature used by a transaction which has no circuit support.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UnsupportedFeature {
+    /// An opcode executed without error which is either unknown to the
+    /// circuits (parsed as `INVALID`) or only handled by a dummy gadget.
+    Opcode(OpcodeId),
+    /// A call to a precompile whose execution is not constrained.
+    Precompile(PrecompileCalls),
+    /// A transaction of this EIP-2718 type, rejected by the builder as its fields
+    /// can not be carried by the tx table.
+    TxType(u64),
+}
+
+/// Scan the transactions of a block along with their traces and return, for
+/// each transaction (by index) once per feature, the [`UnsupportedFeature`]s it
+/// uses. No witness is built, so this is cheap enough to run on every block
+/// before deciding to prove it.
+pub fn find_unsupported_features(
+    txs: &[Transaction],
+    geth_traces: &[GethExecTrace],
+) -> Vec<(usize, UnsupportedFeature)> {
+    let mut found = Vec::new();
+    let mut report = |tx_idx: usize, feature: UnsupportedFeature| {
+        if !found.contains(&(tx_idx, feature)) {
+            found.push((tx_idx, feature));
+        }
+    };
+
+    for (tx_idx, (tx, geth_trace)) in txs.iter().zip(geth_traces).enumerate() {
+        if let Some(tx_type @ (3 | 4)) = tx.transaction_type.map(|tx_type| tx_type.as_u64()) {
+            report(tx_idx, UnsupportedFeature::TxType(tx_type));
+        }
+
+        for step in geth_trace.struct_logs.iter() {
+            match step.op {
+                OpcodeId::SELFDESTRUCT => report(tx_idx, UnsupportedFeature::Opcode(step.op)),
+                OpcodeId::INVALID(_) if step.error.is_none() => {
+                    report(tx_idx, UnsupportedFeature::Opcode(step.op))
+                }
+                _ => {}
+            }
+        }
+
+        for call in geth_trace.call_trace.flatten_trace(&geth_trace.prestate) {
+            let Some(to) = call.to.filter(is_precompiled) else {
+                continue;
+            };
+            let precompile = PrecompileCalls::from(to.0[19]);
+            if matches!(
+                precompile,
+                PrecompileCalls::Ripemd160 | PrecompileCalls::Blake2F
+            ) {
+                report(tx_idx, UnsupportedFeature::Precompile(precompile));
+            }
+        }
+    }
+
+    found
+}
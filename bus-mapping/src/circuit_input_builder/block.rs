@@ -9,8 +9,9 @@ use crate::{
     operation::{OperationContainer, RWCounter},
     Error,
 };
-use eth_types::{Address, Hash, ToWord, Word};
-use std::collections::{BTreeMap, HashMap};
+use eth_types::{Address, Hash, ToWord, Word, H256};
+use ethers_core::utils::keccak256;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 /// Context of a [`Block`] which can mutate in a [`Transaction`].
 #[derive(Debug)]
@@ -178,6 +179,70 @@ impl BlockHead {
     }
 }
 
+/// Estimated witness size of a [`Block`] per sub-circuit, available right after
+/// the circuit inputs are built, before any circuit is synthesized.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BlockRowUsage {
+    /// Rw operations, each taking one row of the state circuit.
+    pub rws: usize,
+    /// Bytes of all copy events, each taking two rows of the copy circuit.
+    pub copy_bytes: usize,
+    /// Keccak-f permutations needed to hash the distinct SHA3 inputs and the
+    /// tx RLPs and signers, as counted by the capacity checks.
+    pub keccak_permutations: usize,
+    /// Steps of all exponentiation events.
+    pub exp_steps: usize,
+    /// Bytecode bytes, plus one header row per bytecode.
+    pub bytecode_bytes: usize,
+}
+
+/// Running count of the distinct keccak inputs of a [`Block`], updated as txs
+/// are handled so the keccak rows can be checked without rescanning the block.
+#[derive(Debug, Default, Clone)]
+struct KeccakUsage {
+    /// Number of `sha3_inputs` already counted.
+    sha3_inputs: usize,
+    /// Number of txs whose RLP inputs are already counted.
+    txs: usize,
+    /// Hashes of the counted inputs, as the keccak circuit hashes each input once.
+    hashes: HashSet<H256>,
+    /// Keccak-f permutations of the counted inputs.
+    permutations: usize,
+}
+
+impl KeccakUsage {
+    fn add(&mut self, input: &[u8]) {
+        if self.hashes.insert(H256(keccak256(input))) {
+            self.permutations += keccak_permutations(input);
+        }
+    }
+
+    /// Count the SHA3 inputs and txs past the ones already counted, and return the
+    /// permutations of all the counted inputs.
+    fn update(&mut self, sha3_inputs: &[Vec<u8>], txs: &[Transaction]) -> usize {
+        for input in &sha3_inputs[self.sha3_inputs..] {
+            self.add(input);
+        }
+        self.sha3_inputs = sha3_inputs.len();
+        for tx in &txs[self.txs..] {
+            self.add(&tx.rlp_bytes);
+            if !tx.tx_type.is_l1_msg() {
+                self.add(&tx.rlp_unsigned_bytes);
+                // the 64 bytes public key of the signer fit in one permutation
+                self.permutations += 1;
+            }
+        }
+        self.txs = txs.len();
+        self.permutations
+    }
+}
+
+/// Keccak-f permutations needed to hash `input`: keccak absorbs 136 bytes per
+/// permutation, with at least one for padding.
+fn keccak_permutations(input: &[u8]) -> usize {
+    input.len() / 136 + 1
+}
+
 /// Circuit Input related to a block.
 #[derive(Debug, Default, Clone)]
 pub struct Block {
@@ -214,6 +279,10 @@ pub struct Block {
     pub precompile_events: PrecompileEvents,
     /// circuit capacity counter
     copy_counter: usize,
+    /// Steps of all exponentiation events, see [`Block::add_exp_event`].
+    exp_steps: usize,
+    /// Keccak inputs counted so far, see [`Block::update_keccak_usage`].
+    keccak_usage: KeccakUsage,
     /// relax mode indicate builder and circuit would skip
     /// some sanity check, used by testing and debugging
     relax_mode: bool,
@@ -327,6 +396,36 @@ impl Block {
             .unwrap_or(self.prev_state_root)
     }
 
+    /// Estimate the witness size of this block per sub-circuit, so callers can
+    /// pick a degree or split the block before synthesizing any circuit.
+    pub fn row_usage(&self) -> BlockRowUsage {
+        let container = &self.container;
+        let rws = container.memory.len()
+            + container.stack.len()
+            + container.storage.len()
+            + container.tx_access_list_account.len()
+            + container.tx_access_list_account_storage.len()
+            + container.tx_refund.len()
+            + container.account.len()
+            + container.call_context.len()
+            + container.tx_receipt.len()
+            + container.tx_log.len()
+            + container.start.len();
+        // count on a copy, as the running usage belongs to the capacity checks
+        let keccak_permutations = self
+            .keccak_usage
+            .clone()
+            .update(&self.sha3_inputs, &self.txs);
+
+        BlockRowUsage {
+            rws,
+            copy_bytes: self.copy_event_total_len(),
+            keccak_permutations,
+            exp_steps: self.exp_events.iter().map(|e| e.steps.len()).sum(),
+            bytecode_bytes: self.code.values().map(|code| code.len() + 1).sum(),
+        }
+    }
+
     #[cfg(test)]
     pub fn txs_mut(&mut self) -> &mut Vec<Transaction> {
         &mut self.txs
@@ -361,8 +460,24 @@ impl Block {
     }
     /// Push an exponentiation event to the block.
     pub fn add_exp_event(&mut self, event: ExpEvent) {
+        self.exp_steps += event.steps.len();
         self.exp_events.push(event);
     }
+    /// Bytes of all copy events pushed so far.
+    pub(crate) fn copy_bytes(&self) -> usize {
+        self.copy_counter
+    }
+    /// Steps of all exponentiation events pushed so far.
+    pub(crate) fn exp_steps(&self) -> usize {
+        self.exp_steps
+    }
+    /// Count the keccak inputs added since the last call: the SHA3 inputs, and the
+    /// tx hash, sign data and public key of each new tx. Inputs hashed only once the
+    /// witness block is built (PI, ecrecover, padding) are not counted, so the
+    /// result is a lower bound of the permutations the keccak circuit needs.
+    pub(crate) fn update_keccak_usage(&mut self) -> usize {
+        self.keccak_usage.update(&self.sha3_inputs, &self.txs)
+    }
     /// Push a precompile event to the block.
     pub fn add_precompile_event(&mut self, event: PrecompileEvent) {
         self.precompile_events.events.push(event);
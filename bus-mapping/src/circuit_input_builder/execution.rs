@@ -151,6 +151,8 @@ pub enum ExecState {
     BeginTx,
     /// Virtual step End Tx
     EndTx,
+    /// Virtual step of a tx which didn't pass the pre-execution checks
+    InvalidTx,
     /// Virtual step End Block
     EndBlock,
 }
@@ -16,8 +16,8 @@ use crate::{
     exec_trace::OperationRef,
     operation::{
         AccountField, AccountOp, CallContextField, CallContextOp, MemoryOp, Op, OpEnum, Operation,
-        StackOp, Target, TxAccessListAccountOp, TxAccessListAccountStorageOp, TxLogField, TxLogOp,
-        TxReceiptField, TxReceiptOp, RW,
+        StackOp, StorageOp, Target, TxAccessListAccountOp, TxAccessListAccountStorageOp,
+        TxLogField, TxLogOp, TxReceiptField, TxReceiptOp, RW,
     },
     precompile::{is_precompiled, PrecompileCalls},
     state_db::{CodeDB, StateDB},
@@ -77,6 +77,16 @@ impl<'a> CircuitInputStateRef<'a> {
         }
     }
 
+    /// Create a new InvalidTx step, no gas is used by an invalid tx
+    pub fn new_invalid_tx_step(&self) -> ExecStep {
+        ExecStep {
+            exec_state: ExecState::InvalidTx,
+            gas_left: Gas(self.tx.gas),
+            rwc: self.block_ctx.rwc,
+            ..Default::default()
+        }
+    }
+
     /// Create a step right after the ref_step, it shared the same
     /// exec_state and call context with ref_step
     pub fn new_next_step(&self, ref_step: &ExecStep) -> Result<ExecStep, Error> {
@@ -177,7 +187,11 @@ impl<'a> CircuitInputStateRef<'a> {
         let rwc = self.block_ctx.rwc.0;
         if rwc > effective_limit {
             log::error!("rwc > max_rws, rwc={}, max_rws={}", rwc, max_rws);
-            return Err(Error::InternalError("rws not enough"));
+            return Err(Error::CapacityExceeded {
+                circuit: "state",
+                used: rwc,
+                limit: effective_limit,
+            });
         };
         Ok(())
     }
@@ -503,8 +517,13 @@ impl<'a> CircuitInputStateRef<'a> {
                 }
                 AccountField::CodeHash => {
                     self.sdb.set_touched(&op.address);
-                    let value = H256::from(op.value.to_be_bytes());
-                    account.code_hash = value;
+                    // code_hash=0 encodes a non-existing (destructed) account in the State
+                    // Circuit, which the StateDB represents with the empty code hash.
+                    account.code_hash = if op.value.is_zero() {
+                        CodeDB::empty_code_hash()
+                    } else {
+                        H256::from(op.value.to_be_bytes())
+                    };
                 }
                 AccountField::CodeSize => {
                     account.code_size = op.value;
@@ -514,6 +533,69 @@ impl<'a> CircuitInputStateRef<'a> {
         self.sdb.set_account(&op.address, account);
     }
 
+    /// Push the reversible writes that destruct the account at `address`: all
+    /// of its non-zero storage slots are cleared and its nonce, code size and
+    /// code hashes are set to 0.  The `CodeHash` write comes last, since
+    /// code_hash=0 encodes a non-existing account in the State Circuit, so that
+    /// every later read of the account in the block observes the cleared
+    /// state.  Moving the balance away is left to the caller.
+    ///
+    /// Only the slots known to the [`StateDB`] are cleared, i.e. the ones in
+    /// the prestate of the block or written in it.  A slot the block never
+    /// accessed keeps its value, so a later SLOAD of it in the same block
+    /// would still read the value from before the destruction.
+    pub fn destruct_account(&mut self, step: &mut ExecStep, address: Address) -> Result<(), Error> {
+        let tx_id = self.tx_ctx.id();
+        for (key, value_prev) in self.sdb.get_storage_slots(&address) {
+            let committed_value = *self.sdb.get_committed_storage(&address, &key).1;
+            self.push_op_reversible(
+                step,
+                StorageOp::new(
+                    address,
+                    key,
+                    Word::zero(),
+                    value_prev,
+                    tx_id,
+                    committed_value,
+                ),
+            )?;
+        }
+
+        let account = self.sdb.get_account(&address).1.clone();
+        self.push_op_reversible(
+            step,
+            AccountOp::new(address, AccountField::Nonce, Word::zero(), account.nonce),
+        )?;
+        self.push_op_reversible(
+            step,
+            AccountOp::new(
+                address,
+                AccountField::CodeSize,
+                Word::zero(),
+                account.code_size,
+            ),
+        )?;
+        #[cfg(feature = "scroll")]
+        self.push_op_reversible(
+            step,
+            AccountOp::new(
+                address,
+                AccountField::KeccakCodeHash,
+                Word::zero(),
+                account.keccak_code_hash.to_word(),
+            ),
+        )?;
+        self.push_op_reversible(
+            step,
+            AccountOp::new(
+                address,
+                AccountField::CodeHash,
+                Word::zero(),
+                account.code_hash.to_word(),
+            ),
+        )
+    }
+
     /// Push a read type [`AccountOp`] into the
     /// [`OperationContainer`](crate::operation::OperationContainer) with the
     /// next [`RWCounter`](crate::operation::RWCounter), and then
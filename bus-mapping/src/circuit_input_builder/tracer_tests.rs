@@ -10,7 +10,7 @@ use eth_types::{
     address, bytecode,
     evm_types::{stack::Stack, Gas, Memory, OpcodeId},
     geth_types::GethData,
-    word, Bytecode, GethExecError, GethExecStep, Hash, ToAddress, ToWord, Word,
+    word, Bytecode, GethExecError, GethExecStep, Hash, ToAddress, ToWord, Withdrawal, Word,
 };
 use mock::test_ctx::{helpers::*, LoggerConfig, TestContext};
 use pretty_assertions::assert_eq;
@@ -40,6 +40,7 @@ impl CircuitInputBuilderTx {
                 l1_fee: 0,
                 gas: Gas(0),
                 failed: false,
+                invalid: false,
                 return_value: "".to_owned(),
                 struct_logs: vec![geth_step.clone()],
                 account_after: vec![],
@@ -1944,3 +1945,39 @@ fn create_address() {
 
     assert_eq!(addr.to_word(), addr_expect);
 }
+
+#[test]
+fn block_with_withdrawals_is_rejected() {
+    let code = bytecode! {
+        STOP
+    };
+    let mut block: GethData = TestContext::<2, 1>::new(
+        None,
+        account_0_code_account_1_no_code(code),
+        tx_from_1_to_0,
+        |block, _tx| block.number(0xcafeu64),
+    )
+    .unwrap()
+    .into();
+
+    // An empty withdrawals list, as in post-Shanghai headers, is accepted.
+    block.eth_block.withdrawals = Some(vec![]);
+    let mut builder =
+        crate::mock::BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+    builder
+        .handle_block(&block.eth_block, &block.geth_traces)
+        .unwrap();
+
+    block.eth_block.withdrawals = Some(vec![Withdrawal {
+        index: 0u64.into(),
+        validator_index: 0u64.into(),
+        address: *ADDR_B,
+        amount: Word::from(1),
+    }]);
+    let mut builder =
+        crate::mock::BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+    assert!(matches!(
+        builder.handle_block(&block.eth_block, &block.geth_traces),
+        Err(Error::InternalError("block withdrawals are not supported"))
+    ));
+}
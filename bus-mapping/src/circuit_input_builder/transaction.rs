@@ -343,6 +343,12 @@ impl Transaction {
             }
         );
 
+        // Neither the rlp circuit nor the tx circuit can decode blob (3) and set code (4) txs.
+        // Besides, the tx and block tables carry neither the blob gas fee nor the authorization
+        // list, so BeginTx could not charge the former or apply the delegations of the latter.
+        if let Some(tx_type @ (3 | 4)) = eth_tx.transaction_type.map(|tx_type| tx_type.as_u64()) {
+            return Err(Error::UnsupportedTxType(tx_type));
+        }
         let tx_type = TxType::get_tx_type(eth_tx);
         let (l1_fee, l1_fee_committed) = if tx_type.is_l1_msg() {
             Default::default()
@@ -39,6 +39,18 @@ pub enum Error {
     ExecutionError(ExecError),
     /// Internal Code error
     InternalError(&'static str),
+    /// The circuits cannot prove transactions of this EIP-2718 type
+    UnsupportedTxType(u64),
+    /// The witness needs more rows than a circuit is configured with in the
+    /// [`CircuitsParams`](crate::circuit_input_builder::CircuitsParams).
+    CapacityExceeded {
+        /// Name of the circuit running out of rows
+        circuit: &'static str,
+        /// Rows needed so far
+        used: usize,
+        /// Rows available
+        limit: usize,
+    },
 }
 
 impl From<eth_types::Error> for Error {
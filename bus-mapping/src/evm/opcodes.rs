@@ -10,7 +10,7 @@ use crate::{
     Error,
 };
 use core::fmt::Debug;
-use eth_types::{evm_unimplemented, GethExecStep, ToAddress, ToWord, Word};
+use eth_types::{evm_unimplemented, GethExecStep, ToAddress, Word};
 
 #[cfg(any(feature = "enable-memory", feature = "enable-stack"))]
 use crate::util::GETH_TRACE_CHECK_LEVEL;
@@ -75,6 +75,8 @@ mod error_write_protection;
 
 #[cfg(all(feature = "enable-memory", test))]
 mod memory_expansion_test;
+#[cfg(all(not(feature = "scroll"), test))]
+mod selfdestruct_test;
 #[cfg(feature = "test")]
 pub use callop::tests::PrecompileCallArgs;
 
@@ -83,7 +85,7 @@ use self::{pushn::PushN, sha3::Sha3};
 use address::Address;
 use arithmetic::ArithmeticOpcode;
 use balance::Balance;
-use begin_end_tx::{gen_begin_tx_steps, gen_end_tx_steps};
+use begin_end_tx::{gen_begin_tx_steps, gen_end_tx_steps, gen_invalid_tx_steps};
 use blockhash::Blockhash;
 use calldatacopy::Calldatacopy;
 use calldataload::Calldataload;
@@ -143,7 +145,7 @@ pub trait Opcode: Debug {
 }
 
 /// Generic trait for tx execution steps
-/// which only supports ExecState::BeginTx and ExecState:EndTx
+/// which only supports ExecState::BeginTx, ExecState::EndTx and ExecState::InvalidTx
 pub trait TxExecSteps: Debug {
     fn gen_associated_steps(
         state: &mut CircuitInputStateRef,
@@ -524,10 +526,17 @@ pub fn gen_associated_steps(
         let ret = gen_end_tx_steps(state)?;
         Ok(vec![ret])
     }
+    fn gen_invalid_tx_steps_adapt(
+        state: &mut CircuitInputStateRef,
+    ) -> Result<Vec<ExecStep>, Error> {
+        let ret = gen_invalid_tx_steps(state)?;
+        Ok(vec![ret])
+    }
 
     let fn_gen_associated_steps = match execution_step {
         ExecState::BeginTx => gen_begin_tx_steps,
         ExecState::EndTx => gen_end_tx_steps_adapt,
+        ExecState::InvalidTx => gen_invalid_tx_steps_adapt,
         _ => {
             unreachable!()
         }
@@ -598,24 +607,7 @@ fn dummy_gen_selfdestruct_ops(
             value_prev: value,
         },
     )?;
-    state.push_op_reversible(
-        &mut exec_step,
-        AccountOp {
-            address: sender,
-            field: AccountField::Nonce,
-            value: Word::zero(),
-            value_prev: sender_account.nonce,
-        },
-    )?;
-    state.push_op_reversible(
-        &mut exec_step,
-        AccountOp {
-            address: sender,
-            field: AccountField::CodeHash,
-            value: Word::zero(),
-            value_prev: sender_account.code_hash.to_word(),
-        },
-    )?;
+    state.destruct_account(&mut exec_step, sender)?;
     if receiver != sender {
         state.transfer_to(
             &mut exec_step,
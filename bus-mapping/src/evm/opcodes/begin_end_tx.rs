@@ -5,7 +5,7 @@ use super::{
 use crate::{
     circuit_input_builder::{
         Call, CircuitInputStateRef, CopyAccessList, CopyBytes, CopyDataType, CopyEvent, ExecStep,
-        NumberOrHash,
+        NumberOrHash, Transaction,
     },
     l2_predeployed::l1_gas_price_oracle,
     operation::{
@@ -17,7 +17,7 @@ use crate::{
 };
 use eth_types::{
     evm_types::{
-        gas_utils::{tx_access_list_gas_cost, tx_data_gas_cost},
+        gas_utils::{tx_access_list_gas_cost, tx_data_gas_cost, tx_intrinsic_gas},
         GasCost, MAX_REFUND_QUOTIENT_OF_GAS_USED,
     },
     Bytecode, ToWord, Word,
@@ -184,27 +184,8 @@ pub fn gen_begin_tx_steps(state: &mut CircuitInputStateRef) -> Result<Vec<ExecSt
         )?;
     }
 
-    // Calculate gas cost of init code only for EIP-3860 of Shanghai.
-    #[cfg(feature = "shanghai")]
-    let init_code_gas_cost = if state.tx.is_create() {
-        (state.tx.input.len() as u64 + 31) / 32 * eth_types::evm_types::INIT_CODE_WORD_GAS
-    } else {
-        0
-    };
-    #[cfg(not(feature = "shanghai"))]
-    let init_code_gas_cost = 0;
-
-    // Calculate intrinsic gas cost
-    let call_data_gas_cost = tx_data_gas_cost(&state.tx.input);
-    let access_list_gas_cost = tx_access_list_gas_cost(&state.tx.access_list);
-    let intrinsic_gas_cost = if state.tx.is_create() {
-        GasCost::CREATION_TX.as_u64()
-    } else {
-        GasCost::TX.as_u64()
-    } + call_data_gas_cost
-        + access_list_gas_cost
-        + init_code_gas_cost;
-    log::trace!("intrinsic_gas_cost {intrinsic_gas_cost}, call_data_gas_cost {call_data_gas_cost}, access_list_gas_cost {access_list_gas_cost}, init_code_gas_cost {init_code_gas_cost}, &mut exec_step.gas_cost {:?}", &mut exec_step.gas_cost);
+    let intrinsic_gas_cost = intrinsic_gas_cost(&state.tx);
+    log::trace!("intrinsic_gas_cost {intrinsic_gas_cost}");
     exec_step.gas_cost = GasCost(intrinsic_gas_cost);
 
     // Get code_hash of callee account
@@ -536,6 +517,61 @@ pub fn gen_begin_tx_steps(state: &mut CircuitInputStateRef) -> Result<Vec<ExecSt
     Ok(std::iter::once(exec_step).chain(precompile_step).collect())
 }
 
+pub fn gen_invalid_tx_steps(state: &mut CircuitInputStateRef) -> Result<ExecStep, Error> {
+    let mut exec_step = state.new_invalid_tx_step();
+    let call = state.call()?.clone();
+
+    // l1 messages are included by the bridge and can never be skipped
+    if state.tx.tx_type.is_l1_msg() {
+        return Err(Error::InternalError("l1 msg tx can not be invalid"));
+    }
+
+    // write tx_id
+    begin_tx(state, &mut exec_step, &call)?;
+
+    // Add 3 RW read operations for transaction L1 fee, which is part of the
+    // minimum balance the caller must hold.
+    gen_tx_l1_fee_ops(state, &mut exec_step)?;
+
+    let caller_address = call.caller_address;
+    let caller_account = state.sdb.get_account(&caller_address).1.clone();
+    state.account_read(
+        &mut exec_step,
+        caller_address,
+        AccountField::Nonce,
+        caller_account.nonce,
+    )?;
+    state.account_read(
+        &mut exec_step,
+        caller_address,
+        AccountField::Balance,
+        caller_account.balance,
+    )?;
+
+    // The circuit can only prove the tx was skipped if at least one of the
+    // pre-execution checks fails, so reject the trace otherwise.
+    let gas_fee_cap = if state.tx.tx_type.is_eip1559() {
+        state.tx.gas_fee_cap
+    } else {
+        state.tx.gas_price
+    };
+    let min_balance = gas_fee_cap * state.tx.gas + state.tx.value + state.tx.l1_fee();
+    let is_nonce_valid = caller_account.nonce == state.tx.nonce.into();
+    let is_gas_sufficient = state.tx.gas >= intrinsic_gas_cost(&state.tx);
+    let is_balance_sufficient = caller_account.balance >= min_balance;
+    log::trace!(
+        "invalid tx: nonce valid {is_nonce_valid}, gas sufficient {is_gas_sufficient}, balance sufficient {is_balance_sufficient}"
+    );
+    if is_nonce_valid && is_gas_sufficient && is_balance_sufficient {
+        return Err(Error::InternalError("tx passes the pre-execution checks"));
+    }
+
+    // The tx is kept in the block with a failed receipt and no gas used.
+    write_tx_receipt(state, &mut exec_step, false)?;
+
+    Ok(exec_step)
+}
+
 pub fn gen_end_tx_steps(state: &mut CircuitInputStateRef) -> Result<ExecStep, Error> {
     let mut exec_step = state.new_end_tx_step();
     let call = state.tx.calls()[0].clone();
@@ -679,6 +715,22 @@ pub(crate) fn end_tx(
     Ok(())
 }
 
+/// Intrinsic gas of a tx, which is charged before its execution begins.
+fn intrinsic_gas_cost(tx: &Transaction) -> u64 {
+    let call_data_gas_cost = tx_data_gas_cost(&tx.input);
+    let access_list_gas_cost = tx_access_list_gas_cost(&tx.access_list);
+    log::trace!(
+        "call_data_gas_cost {call_data_gas_cost}, access_list_gas_cost {access_list_gas_cost}"
+    );
+
+    tx_intrinsic_gas(
+        tx.is_create(),
+        tx.input.len(),
+        call_data_gas_cost,
+        access_list_gas_cost,
+    )
+}
+
 fn write_tx_receipt(
     state: &mut CircuitInputStateRef,
     exec_step: &mut ExecStep,
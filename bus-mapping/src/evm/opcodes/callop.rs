@@ -666,7 +666,8 @@ impl<const N_ARGS: usize> Opcode for CallOpcode<N_ARGS> {
 #[cfg(any(test, feature = "test"))]
 pub mod tests {
 
-    use eth_types::{evm_types::OpcodeId, Bytecode, Word};
+    use crate::precompile::PrecompileCalls;
+    use eth_types::{evm_types::OpcodeId, Address, Bytecode, ToWord, Word};
 
     /// Precompile call args
     pub struct PrecompileCallArgs {
@@ -713,8 +714,64 @@ pub mod tests {
     }
 
     impl PrecompileCallArgs {
+        /// Args of a call to `precompile` with `input` as calldata, stored in memory from offset 0,
+        /// and whose `ret_size` bytes of output are written right after it.
+        pub fn new(
+            name: &'static str,
+            precompile: PrecompileCalls,
+            input: &[u8],
+            ret_size: usize,
+        ) -> Self {
+            let mut setup_code = Bytecode::default();
+            for (i, chunk) in input.chunks(32).enumerate() {
+                let mut word = [0u8; 32];
+                word[..chunk.len()].copy_from_slice(chunk);
+                setup_code
+                    .push(32, Word::from_big_endian(&word))
+                    .push(32, Word::from(i * 32))
+                    .write_op(OpcodeId::MSTORE);
+            }
+            PrecompileCallArgs {
+                name,
+                setup_code,
+                ret_size: Word::from(ret_size),
+                ret_offset: Word::from(input.len().next_multiple_of(32)),
+                call_data_length: Word::from(input.len()),
+                address: Address::from(precompile).to_word(),
+                ..Default::default()
+            }
+        }
+
+        /// Get the setup bytecode for call to a precompiled contract, then store the output
+        /// written to memory in storage, one 32 bytes word per slot starting from slot 0, so
+        /// that it can be checked from the post state.
+        pub fn with_call_op_and_sstore_output(&self, call_op: OpcodeId) -> Bytecode {
+            let mut code = self.gen_code(call_op, None);
+            for slot in 0..self.ret_size.as_usize().div_ceil(32) {
+                code.push(32, self.ret_offset + slot * 32)
+                    .write_op(OpcodeId::MLOAD)
+                    .push(32, Word::from(slot))
+                    .write_op(OpcodeId::SSTORE);
+            }
+            code
+        }
+
         /// Get the setup bytecode for call to a precompiled contract.
         pub fn with_call_op(&self, call_op: OpcodeId) -> Bytecode {
+            self.gen_code(call_op, None)
+        }
+
+        /// Get the setup bytecode for call to a precompiled contract, copying the whole return
+        /// data of the precompile to `dest_offset` with RETURNDATACOPY after the call.
+        pub fn with_call_op_and_return_data_copy(
+            &self,
+            call_op: OpcodeId,
+            dest_offset: Word,
+        ) -> Bytecode {
+            self.gen_code(call_op, Some(dest_offset))
+        }
+
+        fn gen_code(&self, call_op: OpcodeId, return_data_dest: Option<Word>) -> Bytecode {
             assert!(
                 call_op.is_call(),
                 "invalid setup, {call_op:?} is not a call op",
@@ -731,8 +788,19 @@ pub mod tests {
                 .push(32, self.gas)
                 .write_op(call_op)
                 .write_op(OpcodeId::POP);
-            code.write_op(OpcodeId::RETURNDATASIZE)
-                .write_op(OpcodeId::POP);
+            match return_data_dest {
+                Some(dest_offset) => {
+                    // RETURNDATACOPY(dest_offset, 0, RETURNDATASIZE)
+                    code.write_op(OpcodeId::RETURNDATASIZE)
+                        .push(1, Word::zero())
+                        .push(32, dest_offset)
+                        .write_op(OpcodeId::RETURNDATACOPY);
+                }
+                None => {
+                    code.write_op(OpcodeId::RETURNDATASIZE)
+                        .write_op(OpcodeId::POP);
+                }
+            }
             for (offset, _) in self.stack_value.iter().rev() {
                 code.push(32, *offset).write_op(OpcodeId::MLOAD);
             }
@@ -834,6 +902,15 @@ pub mod tests {
                 stack_value: vec![(Word::from(0x20), word!("0123456789ABCDEF0123456789ABCDEF"))],
                 ..Default::default()
             },
+            PrecompileCallArgs {
+                stack_value: vec![(Word::from(0x40), Word::from_big_endian(&[0xab; 32]))],
+                ..PrecompileCallArgs::new(
+                    "identity, unaligned input",
+                    PrecompileCalls::Identity,
+                    &[0xab; 40],
+                    40,
+                )
+            },
             PrecompileCallArgs {
                 name: "modexp",
                 setup_code: bytecode! {
@@ -1075,4 +1152,127 @@ pub mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_precompile_return_data_copy() {
+        use crate::{
+            circuit_input_builder::{CopyDataType, ExecState, NumberOrHash},
+            mock::BlockData,
+        };
+        use eth_types::{bytecode, geth_types::GethData, word};
+        use mock::{
+            test_ctx::helpers::{account_0_code_account_1_no_code, tx_from_1_to_0},
+            TestContext,
+        };
+
+        let input = word!("0123456789ABCDEF0123456789ABCDEF");
+        let test_call = PrecompileCallArgs {
+            name: "identity",
+            setup_code: bytecode! {
+                PUSH16(input)
+                PUSH1(0x00)
+                MSTORE
+            },
+            ret_size: Word::from(0x10),
+            ret_offset: Word::from(0x20),
+            call_data_length: Word::from(0x20),
+            address: Word::from(0x4),
+            ..Default::default()
+        };
+        let code = test_call.with_call_op_and_return_data_copy(OpcodeId::CALL, Word::from(0x40));
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            tx_from_1_to_0,
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        let tx = &builder.block.txs()[0];
+        let precompile_call_id = tx.calls()[1].call_id;
+        assert!(tx
+            .steps()
+            .iter()
+            .any(|step| step.exec_state == ExecState::Op(OpcodeId::RETURNDATACOPY)));
+
+        // The precompile output is written to the callee memory, from where RETURNDATACOPY copies
+        // the whole return data into the caller memory.
+        let copy_events = &builder.block.copy_events;
+        let output_event = copy_events
+            .iter()
+            .find(|event| event.src_type == CopyDataType::RlcAcc)
+            .expect("precompile output copy event");
+        assert_eq!(output_event.dst_type, CopyDataType::Memory);
+        assert_eq!(
+            output_event.dst_id,
+            NumberOrHash::Number(precompile_call_id)
+        );
+
+        let return_data_event = copy_events.last().expect("RETURNDATACOPY copy event");
+        assert_eq!(return_data_event.src_type, CopyDataType::Memory);
+        assert_eq!(
+            return_data_event.src_id,
+            NumberOrHash::Number(precompile_call_id)
+        );
+        assert_eq!(return_data_event.src_addr, 0);
+        assert_eq!(return_data_event.src_addr_end, 0x20);
+        assert_eq!(return_data_event.dst_addr, 0x40);
+        assert_eq!(return_data_event.copy_length(), 0x20);
+    }
+
+    #[test]
+    fn test_precompile_sstore_output() {
+        use crate::{mock::BlockData, operation::RW};
+        use eth_types::geth_types::GethData;
+        use mock::{
+            test_ctx::helpers::{account_0_code_account_1_no_code, tx_from_1_to_0},
+            TestContext,
+        };
+
+        let test_call = PrecompileCallArgs::new(
+            "identity, unaligned input",
+            PrecompileCalls::Identity,
+            &[0xab; 40],
+            40,
+        );
+        let code = test_call.with_call_op_and_sstore_output(OpcodeId::STATICCALL);
+        let block: GethData = TestContext::<2, 1>::new(
+            None,
+            account_0_code_account_1_no_code(code),
+            tx_from_1_to_0,
+            |block, _tx| block,
+        )
+        .unwrap()
+        .into();
+
+        let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+        builder
+            .handle_block(&block.eth_block, &block.geth_traces)
+            .unwrap();
+
+        // The 40 bytes of output take two slots, the second one padded with the
+        // untouched memory after the output.
+        let mut last_slot = [0u8; 32];
+        last_slot[..8].copy_from_slice(&[0xab; 8]);
+        assert_eq!(
+            builder
+                .block
+                .container
+                .storage
+                .iter()
+                .filter(|operation| operation.rw() == RW::WRITE)
+                .map(|operation| (operation.op().key, operation.op().value))
+                .collect::<Vec<_>>(),
+            vec![
+                (Word::from(0), Word::from_big_endian(&[0xab; 32])),
+                (Word::from(1), Word::from_big_endian(&last_slot)),
+            ]
+        );
+    }
 }
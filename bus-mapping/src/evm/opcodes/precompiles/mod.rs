@@ -91,7 +91,9 @@ pub fn gen_ops(
                 return_bytes: return_bytes.to_vec(),
             }),
         ),
-        _ => {
+        // Not supported by the circuits (and always failing on scroll), but the
+        // call bytes are still recorded so the caller side can be constrained.
+        PrecompileCalls::Ripemd160 | PrecompileCalls::Blake2F => {
             log::warn!("precompile {:?} unsupported in circuits", precompile);
             (
                 None,
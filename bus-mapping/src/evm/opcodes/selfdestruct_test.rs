@@ -0,0 +1,79 @@
+use crate::{
+    mock::BlockData,
+    operation::{AccountField, StorageOp, RW},
+};
+use eth_types::{bytecode, geth_types::GethData, ToWord, Word};
+use mock::{test_ctx::helpers::tx_from_1_to_0, TestContext, MOCK_ACCOUNTS};
+use pretty_assertions::assert_eq;
+
+#[test]
+fn selfdestruct_clears_storage_and_code() {
+    let code = bytecode! {
+        PUSH20(MOCK_ACCOUNTS[1].to_word())
+        SELFDESTRUCT
+    };
+    let storage = [
+        (Word::from(1), Word::from(0x22)),
+        (Word::from(2), Word::from(0x33)),
+    ];
+
+    let block: GethData = TestContext::<2, 1>::new(
+        None,
+        |accs| {
+            accs[0]
+                .address(MOCK_ACCOUNTS[0])
+                .balance(Word::from(10u64.pow(19)))
+                .code(code)
+                .storage(storage.into_iter());
+            accs[1]
+                .address(MOCK_ACCOUNTS[1])
+                .balance(Word::from(10u64.pow(19)));
+        },
+        tx_from_1_to_0,
+        |block, _tx| block.number(0xcafeu64),
+    )
+    .unwrap()
+    .into();
+
+    let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+    builder
+        .handle_block(&block.eth_block, &block.geth_traces)
+        .unwrap();
+
+    // The slots are cleared although the tx never accessed them, as the mock
+    // state db holds the whole storage of the account.
+    assert_eq!(
+        builder
+            .block
+            .container
+            .storage
+            .iter()
+            .map(|operation| (operation.rw(), operation.op()))
+            .collect::<Vec<_>>(),
+        storage
+            .iter()
+            .map(|(key, value)| (
+                RW::WRITE,
+                &StorageOp::new(MOCK_ACCOUNTS[0], *key, Word::zero(), *value, 1, *value)
+            ))
+            .collect::<Vec<_>>()
+    );
+
+    // The code hash is cleared last, once the other fields are zero.
+    let destructed_fields = builder
+        .block
+        .container
+        .account
+        .iter()
+        .filter(|operation| {
+            operation.rw() == RW::WRITE
+                && operation.op().address == MOCK_ACCOUNTS[0]
+                && operation.op().field != AccountField::Balance
+        })
+        .map(|operation| (operation.op().field, operation.op().value))
+        .collect::<Vec<_>>();
+    assert_eq!(destructed_fields.last().unwrap().0, AccountField::CodeHash);
+    assert!(destructed_fields.contains(&(AccountField::Nonce, Word::zero())));
+    assert!(destructed_fields.contains(&(AccountField::CodeSize, Word::zero())));
+    assert!(destructed_fields.iter().all(|(_, value)| value.is_zero()));
+}
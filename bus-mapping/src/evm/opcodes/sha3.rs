@@ -5,10 +5,7 @@ use crate::{
     },
     Error,
 };
-use eth_types::{
-    evm_types::memory::{MemoryRange, MemoryWordRange},
-    GethExecStep, Word, U256,
-};
+use eth_types::{evm_types::memory::MemoryWordRange, GethExecStep, Word, U256};
 use ethers_core::utils::keccak256;
 
 use super::Opcode;
@@ -39,13 +36,23 @@ impl Opcode for Sha3 {
                 .extend_at_least(offset.as_usize() + size.as_usize());
         }
 
-        let sha3_input = state
-            .call_ctx()?
-            .memory
-            .read_chunk(MemoryRange::new_with_length(
-                offset.low_u64(),
-                size.low_u64(),
-            ));
+        // The hashed input is sliced out of the word-aligned memory read, so large inputs are only
+        // materialized once. The range is only aligned for a non-empty input, as the offset may
+        // be out of range when the size is 0.
+        let aligned = if size.as_usize() != 0 {
+            let dst_range = MemoryWordRange::align_range(offset.low_u64(), size.low_u64());
+            let mem = state.call_ctx()?.memory.read_chunk(dst_range);
+            Some((dst_range, mem))
+        } else {
+            None
+        };
+        let sha3_input = match &aligned {
+            Some((dst_range, mem)) => {
+                let shift = dst_range.shift().0;
+                mem[shift..shift + size.as_usize()].to_vec()
+            }
+            None => vec![],
+        };
 
         // keccak-256 hash of the given data in memory.
         let sha3 = keccak256(&sha3_input);
@@ -57,9 +64,7 @@ impl Opcode for Sha3 {
         // Memory read operations
         let rw_counter_start = state.block_ctx.rwc;
 
-        let copy_steps = if size.as_usize() != 0 {
-            let dst_range = MemoryWordRange::align_range(offset.low_u64(), size.low_u64());
-            let mem = state.call_ctx()?.memory.read_chunk(dst_range);
+        let copy_steps = if let Some((dst_range, mem)) = aligned {
             // Read step
             let mut chunk_index = dst_range.start_slot().0;
             for _ in 0..dst_range.word_count() {
@@ -3,14 +3,17 @@
 
 use crate::Error;
 use eth_types::{
-    Address, Block, Bytes, EIP1186ProofResponse, GethExecTrace, GethPrestateTrace, Hash,
-    ResultGethExecTraces, ResultGethPrestateTraces, Transaction, Word, H256, U64,
+    Address, Block, Bytes, EIP1186ProofResponse, GethCallTrace, GethExecTrace, GethPrestateTrace,
+    Hash, ResultGethCallTraces, ResultGethExecTraces, ResultGethPrestateTraces, Transaction, Word,
+    H256, U64,
 };
 pub use ethers_core::types::BlockNumber;
-use ethers_providers::JsonRpcClient;
+use ethers_providers::{
+    Http, HttpRateLimitRetryPolicy, JsonRpcClient, RetryClient, RetryClientBuilder,
+};
 use serde::Serialize;
 use serde_json::json;
-use std::collections::HashMap;
+use std::{collections::HashMap, time::Duration};
 
 use crate::util::GETH_TRACE_CHECK_LEVEL;
 
@@ -72,6 +75,46 @@ impl Default for GethLoggerConfig {
 /// needs in order to enable Geth queries.
 pub struct GethClient<P: JsonRpcClient>(pub P);
 
+/// Retry and rate limit settings of a [`GethClient`] built with
+/// [`GethClient::new_with_retry`].
+#[derive(Clone, Copy, Debug)]
+pub struct RpcRetryConfig {
+    /// Max number of retries of a request rejected by the rate limit of the node.
+    pub rate_limit_retries: u32,
+    /// Max number of retries of a request which timed out or failed to connect.
+    pub timeout_retries: u32,
+    /// Backoff before the first retry, doubled on each following one.
+    pub initial_backoff: Duration,
+    /// Compute units per second allowed by the node, used to space out retries.
+    pub compute_units_per_second: u64,
+}
+
+impl Default for RpcRetryConfig {
+    fn default() -> Self {
+        Self {
+            rate_limit_retries: 10,
+            timeout_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            compute_units_per_second: 330,
+        }
+    }
+}
+
+impl GethClient<RetryClient<Http>> {
+    /// Generates a new `GethClient` instance over http, which retries transient
+    /// failures (rate limiting, timeouts) with exponential backoff. Useful when
+    /// generating witnesses against public endpoints.
+    pub fn new_with_retry(provider: Http, config: RpcRetryConfig) -> Self {
+        let provider = RetryClientBuilder::default()
+            .rate_limit_retries(config.rate_limit_retries)
+            .timeout_retries(config.timeout_retries)
+            .initial_backoff(config.initial_backoff)
+            .compute_units_per_second(config.compute_units_per_second)
+            .build(provider, Box::new(HttpRateLimitRetryPolicy));
+        Self(provider)
+    }
+}
+
 impl<P: JsonRpcClient> GethClient<P> {
     /// Generates a new `GethClient` instance.
     pub fn new(provider: P) -> Self {
@@ -274,6 +317,34 @@ impl<P: JsonRpcClient> GethClient<P> {
         Ok(resp)
     }
 
+    /// Call `debug_traceBlockByHash` use callTracer to get the call tree of
+    /// each tx, much cheaper than the struct logs when only calls are needed
+    pub async fn trace_block_calls_by_hash(&self, hash: Hash) -> Result<Vec<GethCallTrace>, Error> {
+        let hash = serialize(&hash);
+        let cfg = serialize(&serde_json::json! ({
+            "tracer": "callTracer",
+            "timeout": "300s",
+        }));
+        let resp: ResultGethCallTraces = self
+            .0
+            .request("debug_traceBlockByHash", [hash, cfg])
+            .await
+            .map_err(|e| Error::JSONRpcError(e.into()))?;
+        Ok(resp.0.into_iter().map(|step| step.result).collect())
+    }
+
+    /// Call `debug_traceTransaction` use callTracer to get the call tree
+    pub async fn trace_tx_calls_by_hash(&self, hash: H256) -> Result<GethCallTrace, Error> {
+        let hash = serialize(&hash);
+        let cfg = serialize(&serde_json::json! ({
+            "tracer": "callTracer",
+        }));
+        self.0
+            .request("debug_traceTransaction", [hash, cfg])
+            .await
+            .map_err(|e| Error::JSONRpcError(e.into()))
+    }
+
     /// Calls `eth_getCode` via JSON-RPC returning a contract code
     pub async fn get_code(
         &self,
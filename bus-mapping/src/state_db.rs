@@ -242,6 +242,23 @@ impl StateDB {
         (found, acc.storage.get_mut(key).expect("key not inserted"))
     }
 
+    /// Get all the non-zero storage slots of the [`Account`] at `addr`, sorted
+    /// by key.  Includes the writes in current tx.
+    pub fn get_storage_slots(&self, addr: &Address) -> Vec<(Word, Word)> {
+        let (_, acc) = self.get_account(addr);
+        let keys = acc.storage.keys().copied().chain(
+            self.dirty_storage
+                .keys()
+                .filter(|(address, _)| address == addr)
+                .map(|(_, key)| *key),
+        );
+        keys.collect::<BTreeSet<_>>()
+            .into_iter()
+            .map(|key| (key, *self.get_storage(addr, &key).1))
+            .filter(|(_, value)| !value.is_zero())
+            .collect()
+    }
+
     /// Set storage value at `addr` and `key`.
     /// Writes into dirty_storage during transaction execution.
     /// After transaction execution, `dirty_storage` is committed into `storage`
@@ -402,4 +419,24 @@ mod statedb_tests {
         assert!(found);
         assert_eq!(value, &Word::from(102));
     }
+
+    #[test]
+    fn storage_slots_include_dirty_writes() {
+        let addr = address!("0x0000000000000000000000000000000000000001");
+        let mut statedb = StateDB::new();
+
+        *statedb.get_storage_mut(&addr, &Word::from(1)).1 = Word::from(10);
+        *statedb.get_storage_mut(&addr, &Word::from(2)).1 = Word::from(20);
+        // Dirty writes override the committed values, zeroed slots are skipped.
+        statedb.set_storage(&addr, &Word::from(2), &Word::zero());
+        statedb.set_storage(&addr, &Word::from(3), &Word::from(30));
+
+        assert_eq!(
+            statedb.get_storage_slots(&addr),
+            vec![
+                (Word::from(1), Word::from(10)),
+                (Word::from(3), Word::from(30))
+            ]
+        );
+    }
 }
@@ -1,6 +1,6 @@
 //! Utility functions to help calculate gas
 
-use super::GasCost;
+use super::{GasCost, CREATE_GAS_PER_CODE_WORD};
 use crate::{AccessList, Word};
 
 /// Calculate memory expansion gas cost by current and next memory word size.
@@ -66,3 +66,24 @@ pub fn tx_data_gas_cost(data: &[u8]) -> u64 {
     data.iter()
         .fold(0, |acc, byte| acc + if *byte == 0 { 4 } else { 16 })
 }
+
+/// Calculate the intrinsic gas of a transaction, which is charged before its execution begins.
+/// The init code of a creation transaction costs `CREATE_GAS_PER_CODE_WORD` per word, which is
+/// non-zero only with EIP-3860 of Shanghai.
+pub fn tx_intrinsic_gas(
+    is_create: bool,
+    call_data_len: usize,
+    call_data_gas_cost: u64,
+    access_list_gas_cost: u64,
+) -> u64 {
+    let (base_gas_cost, init_code_gas_cost) = if is_create {
+        (
+            GasCost::CREATION_TX.as_u64(),
+            (call_data_len as u64 + 31) / 32 * CREATE_GAS_PER_CODE_WORD,
+        )
+    } else {
+        (GasCost::TX.as_u64(), 0)
+    };
+
+    base_gas_cost + call_data_gas_cost + access_list_gas_cost + init_code_gas_cost
+}
@@ -4,8 +4,8 @@
 use crate::l2_types::BlockTrace;
 use crate::{
     sign_types::{biguint_to_32bytes_le, ct_option_ok_or, recover_pk2, SignData, SECP256K1_Q},
-    AccessList, Address, Block, Bytes, Error, GethExecTrace, Hash, ToBigEndian, ToLittleEndian,
-    Word, U64,
+    AccessList, AccessListItem, Address, Block, Bytes, Error, GethExecTrace, Hash, ToBigEndian,
+    ToLittleEndian, Word, U64,
 };
 use ethers_core::types::{
     transaction::eip2718::TypedTransaction, Eip1559TransactionRequest, Eip2930TransactionRequest,
@@ -17,7 +17,7 @@ use num_bigint::BigUint;
 use serde::{Serialize, Serializer};
 use serde_with::serde_as;
 use sha3::{Digest, Keccak256};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use strum_macros::EnumIter;
 
 /// Tx type
@@ -382,6 +382,17 @@ impl Transaction {
             msg_hash,
         })
     }
+
+    /// Return the hash signed by the sender, i.e. the keccak of the unsigned
+    /// RLP envelope of its tx type (see [`get_rlp_unsigned`]).
+    pub fn sighash(&self) -> H256 {
+        H256::from_slice(&Keccak256::digest(&self.rlp_unsigned_bytes))
+    }
+
+    /// Return the tx hash, i.e. the keccak of its signed RLP envelope.
+    pub fn compute_hash(&self) -> H256 {
+        H256::from_slice(&Keccak256::digest(&self.rlp_bytes))
+    }
 }
 
 /// GethData is a type that contains all the information of a Ethereum block
@@ -438,3 +449,164 @@ pub fn access_list_size(access_list: &Option<AccessList>) -> (u64, u64) {
         },
     )
 }
+
+/// Build an access list warming the given addresses and storage keys, in
+/// address order.
+pub fn access_list_from_touched(touched: &BTreeMap<Address, BTreeSet<H256>>) -> AccessList {
+    AccessList(
+        touched
+            .iter()
+            .map(|(address, keys)| AccessListItem {
+                address: *address,
+                storage_keys: keys.iter().copied().collect(),
+            })
+            .collect(),
+    )
+}
+
+/// Merge access lists into one listing each address and each of its storage
+/// keys once, in first-seen order. Merging a single list dedups it.
+///
+/// Note that duplicated entries are valid in a tx and charged intrinsic gas,
+/// so this must not be applied to the access list of a signed tx.
+pub fn merge_access_lists(lists: impl IntoIterator<Item = AccessList>) -> AccessList {
+    let mut merged: Vec<AccessListItem> = Vec::new();
+    for item in lists.into_iter().flat_map(|list| list.0) {
+        let entry = match merged.iter_mut().find(|m| m.address == item.address) {
+            Some(entry) => entry,
+            None => {
+                merged.push(AccessListItem {
+                    address: item.address,
+                    storage_keys: vec![],
+                });
+                merged.last_mut().unwrap()
+            }
+        };
+        for key in item.storage_keys {
+            if !entry.storage_keys.contains(&key) {
+                entry.storage_keys.push(key);
+            }
+        }
+    }
+    AccessList(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers_core::{
+        k256::ecdsa::SigningKey,
+        types::Signature,
+        utils::{keccak256, secret_key_to_address},
+    };
+
+    fn signed_tx(tx_type: TxType, typed_tx: &TypedTransaction) -> (Transaction, Signature) {
+        let sk = SigningKey::from_bytes(&[0x42; 32].into()).unwrap();
+        let (sig, recovery_id) = sk
+            .sign_prehash_recoverable(typed_tx.sighash().as_bytes())
+            .unwrap();
+        let chain_id = typed_tx.chain_id().unwrap();
+        let v = match tx_type {
+            TxType::Eip155 => chain_id.as_u64() * 2 + 35 + recovery_id.to_byte() as u64,
+            _ => recovery_id.to_byte() as u64,
+        };
+        let sig = Signature {
+            r: Word::from_big_endian(&sig.r().to_bytes()),
+            s: Word::from_big_endian(&sig.s().to_bytes()),
+            v,
+        };
+        let eth_tx = crate::Transaction {
+            transaction_type: match tx_type {
+                TxType::Eip155 => None,
+                _ => Some(U64::from(2)),
+            },
+            chain_id: Some(chain_id.as_u64().into()),
+            from: secret_key_to_address(&sk),
+            to: typed_tx.to().and_then(|to| to.as_address()).copied(),
+            nonce: *typed_tx.nonce().unwrap(),
+            gas: *typed_tx.gas().unwrap(),
+            value: *typed_tx.value().unwrap(),
+            input: typed_tx.data().unwrap().clone(),
+            gas_price: typed_tx.gas_price(),
+            max_fee_per_gas: typed_tx.as_eip1559_ref().and_then(|tx| tx.max_fee_per_gas),
+            max_priority_fee_per_gas: typed_tx
+                .as_eip1559_ref()
+                .and_then(|tx| tx.max_priority_fee_per_gas),
+            access_list: typed_tx.access_list().cloned(),
+            v: sig.v.into(),
+            r: sig.r,
+            s: sig.s,
+            ..Default::default()
+        };
+        ((&eth_tx).into(), sig)
+    }
+
+    #[test]
+    fn merge_access_lists_dedups() {
+        let (a, b) = (Address::repeat_byte(0xa), Address::repeat_byte(0xb));
+        let (k1, k2) = (H256::repeat_byte(1), H256::repeat_byte(2));
+        let lhs = access_list_from_touched(&BTreeMap::from([
+            (b, BTreeSet::from([k1])),
+            (a, BTreeSet::new()),
+        ]));
+        assert_eq!(lhs.0[0].address, a);
+
+        let rhs = AccessList(vec![
+            AccessListItem {
+                address: b,
+                storage_keys: vec![k2, k1, k2],
+            },
+            AccessListItem {
+                address: a,
+                storage_keys: vec![],
+            },
+        ]);
+        let merged = merge_access_lists([lhs, rhs]);
+        assert_eq!(
+            merged.0,
+            vec![
+                AccessListItem {
+                    address: a,
+                    storage_keys: vec![],
+                },
+                AccessListItem {
+                    address: b,
+                    storage_keys: vec![k1, k2],
+                },
+            ]
+        );
+        assert_eq!(access_list_size(&Some(merged)), (2, 2));
+    }
+
+    #[test]
+    fn sighash_matches_ethers() {
+        let eip155: TypedTransaction = TransactionRequest::new()
+            .to(Address::repeat_byte(0x11))
+            .value(1000)
+            .nonce(3)
+            .gas(21000)
+            .gas_price(7)
+            .chain_id(534352)
+            .into();
+        let eip1559: TypedTransaction = Eip1559TransactionRequest::new()
+            .to(Address::repeat_byte(0x11))
+            .value(1000)
+            .nonce(3)
+            .gas(21000)
+            .max_fee_per_gas(7)
+            .max_priority_fee_per_gas(2)
+            .data(vec![1, 2, 3])
+            .chain_id(534352)
+            .into();
+
+        for (tx_type, typed_tx) in [(TxType::Eip155, eip155), (TxType::Eip1559, eip1559)] {
+            let (tx, sig) = signed_tx(tx_type, &typed_tx);
+            assert_eq!(tx.tx_type, tx_type);
+            assert_eq!(tx.sighash(), typed_tx.sighash());
+            assert_eq!(
+                tx.compute_hash(),
+                H256(keccak256(typed_tx.rlp_signed(&sig)))
+            );
+        }
+    }
+}
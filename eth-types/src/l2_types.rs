@@ -219,6 +219,7 @@ impl From<ExecutionResult> for GethExecTrace {
             l1_fee: e.l1_fee.as_u64(),
             gas: Gas(e.gas),
             failed: e.failed,
+            invalid: false,
             return_value: e.return_value,
             struct_logs,
             account_after: e.account_after,
@@ -39,7 +39,7 @@ pub use ethers_core::{
             eip2930::{AccessList, AccessListItem},
             response::Transaction,
         },
-        Address, Block, Bytes, Signature, H160, H256, H64, U256, U64,
+        Address, Block, Bytes, Signature, Withdrawal, H160, H256, H64, U256, U64,
     },
 };
 use halo2_base::utils::ScalarField;
@@ -516,71 +516,67 @@ impl Serialize for GethExecError {
     }
 }
 
-impl<'de> Deserialize<'de> for GethExecError {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: Deserializer<'de>,
-    {
-        struct GethExecErrorVisitor;
+impl FromStr for GethExecError {
+    type Err = Error;
 
+    /// Parse an error string reported by the geth struct logger, as printed by
+    /// the [`Display`] implementation.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
         static STACK_UNDERFLOW_RE: LazyLock<regex::Regex> =
             LazyLock::new(|| regex::Regex::new(r"^stack underflow \((\d+) <=> (\d+)\)$").unwrap());
         static STACK_OVERFLOW_RE: LazyLock<regex::Regex> =
             LazyLock::new(|| regex::Regex::new(r"^stack limit reached (\d+) \((\d+)\)$").unwrap());
 
-        impl<'de> de::Visitor<'de> for GethExecErrorVisitor {
-            type Value = GethExecError;
+        let unknown = || Error::TracingError(format!("unknown geth exec error: {s}"));
+        let parse_pair = |re: &regex::Regex| {
+            let caps = re.captures(s).ok_or_else(unknown)?;
+            let parse = |i: usize| caps[i].parse::<u64>().map_err(|_| unknown());
+            Ok::<_, Error>((parse(1)?, parse(2)?))
+        };
 
-            fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
-                write!(formatter, "a geth struct logger error string constant")
+        Ok(match s {
+            "out of gas" => GethExecError::OutOfGas,
+            "contract creation code storage out of gas" => GethExecError::CodeStoreOutOfGas,
+            "max call depth exceeded" => GethExecError::Depth,
+            "insufficient balance for transfer" => GethExecError::InsufficientBalance,
+            "contract address collision" => GethExecError::ContractAddressCollision,
+            "execution reverted" => GethExecError::ExecutionReverted,
+            "max initcode size exceeded" => GethExecError::MaxInitCodeSizeExceeded,
+            "max code size exceeded" => GethExecError::MaxCodeSizeExceeded,
+            "invalid jump destination" => GethExecError::InvalidJump,
+            "write protection" => GethExecError::WriteProtection,
+            "return data out of bounds" => GethExecError::ReturnDataOutOfBounds,
+            "gas uint64 overflow" => GethExecError::GasUintOverflow,
+            "invalid code: must not begin with 0xef" => GethExecError::InvalidCode,
+            "nonce uint64 overflow" => GethExecError::NonceUintOverflow,
+            _ if s.starts_with("stack underflow") => {
+                let (stack_len, required) = parse_pair(&STACK_UNDERFLOW_RE)?;
+                GethExecError::StackUnderflow {
+                    stack_len,
+                    required,
+                }
             }
-
-            fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
-            where
-                E: de::Error,
-            {
-                let e = match v {
-                    "out of gas" => GethExecError::OutOfGas,
-                    "contract creation code storage out of gas" => GethExecError::CodeStoreOutOfGas,
-                    "max call depth exceeded" => GethExecError::Depth,
-                    "insufficient balance for transfer" => GethExecError::InsufficientBalance,
-                    "contract address collision" => GethExecError::ContractAddressCollision,
-                    "execution reverted" => GethExecError::ExecutionReverted,
-                    "max initcode size exceeded" => GethExecError::MaxInitCodeSizeExceeded,
-                    "max code size exceeded" => GethExecError::MaxCodeSizeExceeded,
-                    "invalid jump destination" => GethExecError::InvalidJump,
-                    "write protection" => GethExecError::WriteProtection,
-                    "return data out of bounds" => GethExecError::ReturnDataOutOfBounds,
-                    "gas uint64 overflow" => GethExecError::GasUintOverflow,
-                    "invalid code: must not begin with 0xef" => GethExecError::InvalidCode,
-                    "nonce uint64 overflow" => GethExecError::NonceUintOverflow,
-                    _ if v.starts_with("stack underflow") => {
-                        let caps = STACK_UNDERFLOW_RE.captures(v).unwrap();
-                        let stack_len = caps.get(1).unwrap().as_str().parse::<u64>().unwrap();
-                        let required = caps.get(2).unwrap().as_str().parse::<u64>().unwrap();
-                        GethExecError::StackUnderflow {
-                            stack_len,
-                            required,
-                        }
-                    }
-                    _ if v.starts_with("stack limit reached") => {
-                        let caps = STACK_OVERFLOW_RE.captures(v).unwrap();
-                        let stack_len = caps.get(1).unwrap().as_str().parse::<u64>().unwrap();
-                        let limit = caps.get(2).unwrap().as_str().parse::<u64>().unwrap();
-                        GethExecError::StackOverflow { stack_len, limit }
-                    }
-                    _ if v.starts_with("invalid opcode") => v
-                        .strip_prefix("invalid opcode: ")
-                        .map(|s| OpcodeId::from_str(s).unwrap())
-                        .map(GethExecError::InvalidOpcode)
-                        .unwrap(),
-                    _ => return Err(E::invalid_value(de::Unexpected::Str(v), &self)),
-                };
-                Ok(e)
+            _ if s.starts_with("stack limit reached") => {
+                let (stack_len, limit) = parse_pair(&STACK_OVERFLOW_RE)?;
+                GethExecError::StackOverflow { stack_len, limit }
             }
-        }
+            _ if s.starts_with("invalid opcode") => s
+                .strip_prefix("invalid opcode: ")
+                .ok_or_else(unknown)
+                .and_then(OpcodeId::from_str)
+                .map(GethExecError::InvalidOpcode)?,
+            _ => return Err(unknown()),
+        })
+    }
+}
 
-        deserializer.deserialize_str(GethExecErrorVisitor)
+impl<'de> Deserialize<'de> for GethExecError {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
     }
 }
 
@@ -688,6 +684,10 @@ pub struct GethExecTrace {
     pub gas: Gas,
     /// True when the transaction has failed.
     pub failed: bool,
+    /// True when the transaction did not pass the pre-execution checks (nonce, balance or
+    /// intrinsic gas). Such a transaction is kept in the block but leaves the state untouched.
+    #[serde(default)]
+    pub invalid: bool,
     /// Return value of execution which is a hex encoded byte array
     #[serde(rename = "returnValue")]
     pub return_value: String,
@@ -716,6 +716,18 @@ where
     Deserialize::deserialize(d).map(|x: Option<_>| x.unwrap_or_default())
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[doc(hidden)]
+pub struct ResultGethCallTraces(pub Vec<ResultGethCallTrace>);
+
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
+#[doc(hidden)]
+pub struct ResultGethCallTrace {
+    #[serde(rename = "txHash", default)]
+    pub tx_hash: H256,
+    pub result: GethCallTrace,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Deserialize)]
 #[doc(hidden)]
 pub struct ResultGethPrestateTraces(pub Vec<ResultGethPrestateTrace>);
@@ -746,18 +758,25 @@ pub struct GethPrestateTrace {
 /// using callTracer
 #[derive(Deserialize, Serialize, Clone, Debug, Eq, PartialEq)]
 pub struct GethCallTrace {
+    /// sub calls made by this call, in execution order
     #[serde(default)]
-    calls: Vec<GethCallTrace>,
-    error: Option<String>,
-    from: Address,
+    pub calls: Vec<GethCallTrace>,
+    /// error of the call, if it failed
+    pub error: Option<String>,
+    /// caller
+    pub from: Address,
     // gas: U256,
+    /// gas used by the call, including sub calls
     #[serde(rename = "gasUsed")]
-    gas_used: U256,
+    pub gas_used: U256,
     // input: Bytes,
-    output: Option<Bytes>,
-    to: Option<Address>,
+    /// returned data
+    pub output: Option<Bytes>,
+    /// callee, or the created contract
+    pub to: Option<Address>,
+    /// opcode of the call (CALL, CREATE2, ...)
     #[serde(rename = "type")]
-    call_type: String,
+    pub call_type: String,
     // value: U256,
 }
 
@@ -979,6 +998,7 @@ mod tests {
                 l1_fee: 0,
                 gas: Gas(26809),
                 failed: false,
+                invalid: false,
                 return_value: "".to_owned(),
                 account_after: Vec::new(),
                 struct_logs: vec![
@@ -1165,4 +1185,31 @@ mod eth_types_test {
         assert_eq!(req.to, None);
         Ok(())
     }
+
+    #[test]
+    fn geth_exec_error_from_str() {
+        for error in [
+            GethExecError::OutOfGas,
+            GethExecError::InvalidCode,
+            GethExecError::StackUnderflow {
+                stack_len: 1,
+                required: 2,
+            },
+            GethExecError::StackOverflow {
+                stack_len: 1024,
+                limit: 1023,
+            },
+            GethExecError::InvalidOpcode(OpcodeId::ADD),
+        ] {
+            assert_eq!(error.to_string().parse::<GethExecError>().unwrap(), error);
+        }
+        assert_eq!(
+            "invalid opcode: opcode 0xfe not defined"
+                .parse::<GethExecError>()
+                .unwrap(),
+            GethExecError::InvalidOpcode(OpcodeId::INVALID(0xfe))
+        );
+        assert!("stack underflow".parse::<GethExecError>().is_err());
+        assert!("out of gas!".parse::<GethExecError>().is_err());
+    }
 }
@@ -100,6 +100,26 @@ where
         )
     }
 
+    /// Returns a function that can evaluate to a binary expression, that
+    /// evaluates to 1 if value is equal to any of the given (distinct) values.
+    /// Since the equalities are mutually exclusive, this is their sum, of
+    /// degree N.
+    pub fn value_equals_any<F: Field, S: AsBits<N>>(
+        &self,
+        values: impl IntoIterator<Item = S>,
+        rotation: Rotation,
+    ) -> impl FnOnce(&mut VirtualCells<'_, F>) -> Expression<F> {
+        let bits = self.bits;
+        let values: Vec<S> = values.into_iter().collect();
+        move |meta| {
+            let expressions = bits.map(|bit| meta.query_advice(bit, rotation));
+            values
+                .into_iter()
+                .map(|value| Self::value_equals_expr(value, expressions.clone()))
+                .fold(0.expr(), |acc, equals| acc + equals)
+        }
+    }
+
     /// Annotates columns of this gadget embedded within a circuit region.
     pub fn annotate_columns_in_region<F: Field>(&self, region: &mut Region<F>, prefix: &str) {
         let mut annotations = Vec::new();
@@ -210,3 +230,128 @@ pub fn from_bits(bits: &[bool]) -> usize {
     bits.iter()
         .fold(0, |result, &bit| bit as usize + 2 * result)
 }
+
+#[cfg(test)]
+mod test {
+    use super::{BinaryNumberChip, BinaryNumberConfig};
+    use eth_types::Field;
+    use halo2_proofs::{
+        circuit::{Layouter, SimpleFloorPlanner, Value},
+        dev::MockProver,
+        halo2curves::bn256::Fr as Fp,
+        plonk::{Advice, Circuit, Column, ConstraintSystem, Error, Fixed},
+        poly::Rotation,
+    };
+    use std::marker::PhantomData;
+    use strum_macros::EnumIter;
+
+    #[derive(Clone, Copy, Debug, EnumIter)]
+    enum Tag {
+        A,
+        B,
+        C,
+        D,
+        E,
+    }
+
+    impl From<Tag> for usize {
+        fn from(tag: Tag) -> usize {
+            tag as usize
+        }
+    }
+
+    const SET: [Tag; 2] = [Tag::B, Tag::D];
+
+    #[derive(Clone, Debug)]
+    struct TestCircuitConfig {
+        q_enable: Column<Fixed>,
+        check: Column<Advice>,
+        tag: BinaryNumberConfig<Tag, 3>,
+    }
+
+    #[derive(Default)]
+    struct TestCircuit<F: Field> {
+        // checks[i] = tags[i] is one of SET
+        tags: Vec<Tag>,
+        checks: Vec<bool>,
+        _marker: PhantomData<F>,
+    }
+
+    impl<F: Field> Circuit<F> for TestCircuit<F> {
+        type Config = TestCircuitConfig;
+        type FloorPlanner = SimpleFloorPlanner;
+        #[cfg(feature = "circuit-params")]
+        type Params = ();
+
+        fn without_witnesses(&self) -> Self {
+            Self::default()
+        }
+
+        fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+            let q_enable = meta.fixed_column();
+            let check = meta.advice_column();
+            let tag = BinaryNumberChip::configure(meta, q_enable, None);
+
+            meta.create_gate("check value_equals_any", |meta| {
+                let q_enable = meta.query_fixed(q_enable, Rotation::cur());
+                let check = meta.query_advice(check, Rotation::cur());
+                vec![q_enable * (tag.value_equals_any(SET, Rotation::cur())(meta) - check)]
+            });
+
+            TestCircuitConfig {
+                q_enable,
+                check,
+                tag,
+            }
+        }
+
+        fn synthesize(
+            &self,
+            config: Self::Config,
+            mut layouter: impl Layouter<F>,
+        ) -> Result<(), Error> {
+            let chip = BinaryNumberChip::construct(config.tag);
+            layouter.assign_region(
+                || "witness",
+                |mut region| {
+                    for (offset, (tag, &check)) in self.tags.iter().zip(&self.checks).enumerate() {
+                        region.assign_fixed(
+                            || "q_enable",
+                            config.q_enable,
+                            offset,
+                            || Value::known(F::ONE),
+                        )?;
+                        region.assign_advice(
+                            || "check",
+                            config.check,
+                            offset,
+                            || Value::known(F::from(check as u64)),
+                        )?;
+                        chip.assign(&mut region, offset, tag)?;
+                    }
+                    Ok(())
+                },
+            )
+        }
+    }
+
+    fn verify(tags: Vec<Tag>, checks: Vec<bool>) -> bool {
+        let circuit = TestCircuit::<Fp> {
+            tags,
+            checks,
+            _marker: PhantomData,
+        };
+        let prover = MockProver::<Fp>::run(6, &circuit, vec![]).unwrap();
+        prover.verify_par().is_ok()
+    }
+
+    #[test]
+    fn value_equals_any() {
+        assert!(verify(
+            vec![Tag::A, Tag::B, Tag::C, Tag::D, Tag::E],
+            vec![false, true, false, true, false],
+        ));
+        assert!(!verify(vec![Tag::B], vec![false]));
+        assert!(!verify(vec![Tag::C], vec![true]));
+    }
+}
@@ -141,6 +141,69 @@ pub mod select {
     }
 }
 
+/// Returns the random linear combination of the inputs.
+/// Encoding is done as follows: v_0 * R^0 + v_1 * R^1 + ...
+pub mod rlc {
+    use std::ops::{Add, Mul};
+
+    use crate::util::Expr;
+    use eth_types::Field;
+    use halo2_proofs::{circuit::Value, plonk::Expression};
+
+    /// Returns the random linear combination of the expressions, `0` when empty.
+    pub fn expr<F: Field, E: Expr<F>>(expressions: &[E], randomness: E) -> Expression<F> {
+        if !expressions.is_empty() {
+            generic(expressions.iter().map(|e| e.expr()), randomness.expr())
+        } else {
+            0.expr()
+        }
+    }
+
+    /// Returns the random linear combination of the bytes, `0` when empty.
+    pub fn value<'a, F: Field, I>(values: I, randomness: F) -> F
+    where
+        I: IntoIterator<Item = &'a u8>,
+        <I as IntoIterator>::IntoIter: DoubleEndedIterator,
+    {
+        let values = values
+            .into_iter()
+            .map(|v| F::from(*v as u64))
+            .collect::<Vec<F>>();
+        if !values.is_empty() {
+            generic(values, randomness)
+        } else {
+            F::ZERO
+        }
+    }
+
+    /// Returns the random linear combination of the bytes read in big-endian
+    /// order, i.e. `v_0 * R^(n-1) + ... + v_(n-1) * R^0`, for a randomness
+    /// which is only known once the challenge has been squeezed.
+    pub fn value_be<F: Field>(bytes: &[u8], randomness: Value<F>) -> Value<F> {
+        bytes.iter().fold(Value::known(F::ZERO), |acc, byte| {
+            accumulate(acc, *byte, randomness)
+        })
+    }
+
+    /// Appends one byte to a big-endian random linear combination, as done
+    /// row by row by the circuits which accumulate it incrementally.
+    pub fn accumulate<F: Field>(acc: Value<F>, byte: u8, randomness: Value<F>) -> Value<F> {
+        acc * randomness + Value::known(F::from(byte as u64))
+    }
+
+    fn generic<V, I>(values: I, randomness: V) -> V
+    where
+        I: IntoIterator<Item = V>,
+        <I as IntoIterator>::IntoIter: DoubleEndedIterator,
+        V: Clone + Add<Output = V> + Mul<Output = V>,
+    {
+        let mut values = values.into_iter().rev();
+        let init = values.next().expect("values should not be empty");
+
+        values.fold(init, |acc, value| acc * randomness.clone() + value)
+    }
+}
+
 /// Trait that implements functionality to get a constant expression from
 /// commonly used types.
 pub trait Expr<F: Field> {
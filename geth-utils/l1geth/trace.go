@@ -2,6 +2,7 @@ package main
 
 import (
 	"encoding/json"
+	"errors"
 	"fmt"
 	"math/big"
 
@@ -26,6 +27,7 @@ import (
 type ExecutionResult struct {
 	Gas         uint64          `json:"gas"`
 	Failed      bool            `json:"failed"`
+	Invalid     bool            `json:"invalid"`
 	ReturnValue string          `json:"returnValue"`
 	StructLogs  []StructLogRes  `json:"structLogs"`
 	Prestate    json.RawMessage `json:"prestate"`
@@ -263,9 +265,20 @@ func Trace(config TraceConfig) ([]*ExecutionResult, error) {
 		)
 		evm := vm.NewEVM(blockCtx, txContext, stateDB, &chainConfig, vm.Config{Tracer: tracer, NoBaseFee: true})
 
+		snapshot := stateDB.Snapshot()
 		result, err := core.ApplyMessage(evm, &message, new(core.GasPool).AddGas(message.GasLimit))
 		if err != nil {
-			return nil, fmt.Errorf("Failed to apply config.Transactions[%d]: %w", i, err)
+			if !isInvalidTxError(err) {
+				return nil, fmt.Errorf("Failed to apply config.Transactions[%d]: %w", i, err)
+			}
+			// The pre-execution checks may have already bought the gas, roll it back since an
+			// invalid tx must not touch the state.
+			stateDB.RevertToSnapshot(snapshot)
+			executionResults[i], err = invalidExecutionResult(stateDB, &message)
+			if err != nil {
+				return nil, fmt.Errorf("Failed to build result of invalid config.Transactions[%d]: %w", i, err)
+			}
+			continue
 		}
 		stateDB.Finalise(true)
 
@@ -292,6 +305,57 @@ func Trace(config TraceConfig) ([]*ExecutionResult, error) {
 	return executionResults, nil
 }
 
+// isInvalidTxError returns whether err comes from the pre-execution checks of a transaction,
+// in which case the transaction is kept as an invalid one instead of aborting the trace.
+func isInvalidTxError(err error) bool {
+	return errors.Is(err, core.ErrNonceTooLow) ||
+		errors.Is(err, core.ErrNonceTooHigh) ||
+		errors.Is(err, core.ErrInsufficientFunds) ||
+		errors.Is(err, core.ErrInsufficientFundsForTransfer) ||
+		errors.Is(err, core.ErrIntrinsicGas)
+}
+
+// invalidExecutionResult builds the result of a transaction which didn't pass the
+// pre-execution checks: it uses no gas, has no steps and only touches its sender, whose nonce
+// and balance are checked by the circuit and thus make up the prestate.
+func invalidExecutionResult(stateDB *state.StateDB, message *core.Message) (*ExecutionResult, error) {
+	callType := "CALL"
+	if message.To == nil {
+		callType = "CREATE"
+	}
+	callTrace, err := json.Marshal(map[string]interface{}{
+		"from":    message.From,
+		"to":      message.To,
+		"gasUsed": "0x0",
+		"type":    callType,
+	})
+	if err != nil {
+		return nil, err
+	}
+	// Same format as the account of the prestateTracer
+	sender := map[string]interface{}{
+		"balance": (*hexutil.Big)(stateDB.GetBalance(message.From)),
+		"nonce":   stateDB.GetNonce(message.From),
+	}
+	if code := stateDB.GetCode(message.From); len(code) > 0 {
+		sender["code"] = hexutil.Bytes(code)
+	}
+	prestate, err := json.Marshal(map[common.Address]interface{}{message.From: sender})
+	if err != nil {
+		return nil, err
+	}
+
+	return &ExecutionResult{
+		Gas:         0,
+		Failed:      true,
+		Invalid:     true,
+		ReturnValue: "",
+		StructLogs:  []StructLogRes{},
+		Prestate:    prestate,
+		CallTrace:   callTrace,
+	}, nil
+}
+
 type MuxTracer struct {
 	tracers []vm.EVMLogger
 }
@@ -301,7 +301,9 @@ pub fn gen_trace_config(
 pub mod helpers {
     use super::*;
     use crate::{MOCK_ACCOUNTS, MOCK_WALLETS};
+    use eth_types::{evm_types::OpcodeId, Address, Bytes};
     use ethers_signers::Signer;
+    use std::collections::HashMap;
 
     /// Generate a simple setup which adds balance to two default accounts from
     /// [`static@MOCK_ACCOUNTS`]:
@@ -340,4 +342,70 @@ pub mod helpers {
     pub fn tx_from_1_to_0(mut txs: Vec<&mut MockTransaction>, accs: [MockAccount; 2]) {
         txs[0].from(accs[1].address).to(accs[0].address);
     }
+
+    /// Generate a setup for blocks with many transactions: the first
+    /// `n_wallets` accounts are the first [`static@MOCK_WALLETS`], funded with
+    /// 10 ETH each, and the following ones hold `contracts`, in order, at the
+    /// [`static@MOCK_ACCOUNTS`] addresses.
+    pub fn wallets_and_contracts<const NACC: usize>(
+        n_wallets: usize,
+        contracts: Vec<Bytecode>,
+    ) -> impl FnOnce([&mut MockAccount; NACC]) {
+        assert!(n_wallets <= MOCK_WALLETS.len(), "not enough mock wallets");
+        assert!(
+            contracts.len() <= MOCK_ACCOUNTS.len(),
+            "not enough mock accounts"
+        );
+        assert_eq!(n_wallets + contracts.len(), NACC, "Mismatched acc len");
+        move |accs| {
+            let mut accs = accs.into_iter();
+            for (acc, wallet) in accs.by_ref().take(n_wallets).zip(MOCK_WALLETS.iter()) {
+                acc.address(wallet.address()).balance(eth(10));
+            }
+            for ((acc, address), code) in accs.zip(MOCK_ACCOUNTS.iter()).zip(contracts) {
+                acc.address(*address).balance(eth(10)).code(code);
+            }
+        }
+    }
+
+    /// Set the nonce of every transaction to the next nonce of its sender,
+    /// starting from the nonce of the sender account, so that transactions of
+    /// several senders can be interleaved in any order within the block.
+    pub fn sequential_nonces(txs: &mut [&mut MockTransaction], accs: &[MockAccount]) {
+        let mut nonces: HashMap<Address, Word> =
+            accs.iter().map(|acc| (acc.address, acc.nonce)).collect();
+        for tx in txs.iter_mut() {
+            let nonce = nonces.entry(tx.from.address()).or_default();
+            tx.nonce(*nonce);
+            *nonce += Word::one();
+        }
+    }
+
+    /// Returns the input of a contract creation transaction deploying `code`:
+    /// a constructor which copies the code appended to it and returns it.
+    pub fn deployment_input(code: &Bytecode) -> Bytes {
+        let code = code.code();
+        let len = u16::try_from(code.len())
+            .expect("code too long to deploy")
+            .to_be_bytes();
+        let constructor = [
+            OpcodeId::PUSH2.as_u8(),
+            len[0],
+            len[1],
+            OpcodeId::DUP1.as_u8(),
+            OpcodeId::PUSH1.as_u8(),
+            12, // constructor length
+            OpcodeId::PUSH1.as_u8(),
+            0,
+            OpcodeId::CODECOPY.as_u8(),
+            OpcodeId::PUSH1.as_u8(),
+            0,
+            OpcodeId::RETURN.as_u8(),
+        ];
+        constructor
+            .into_iter()
+            .chain(code)
+            .collect::<Vec<_>>()
+            .into()
+    }
 }
@@ -7,7 +7,10 @@ use eth_types::{
 };
 use ethers_core::{
     rand::{CryptoRng, RngCore},
-    types::{OtherFields, TransactionRequest},
+    types::{
+        transaction::eip2718::TypedTransaction, Eip1559TransactionRequest,
+        Eip2930TransactionRequest, OtherFields, TransactionRequest,
+    },
 };
 use ethers_signers::{LocalWallet, Signer};
 use rand::SeedableRng;
@@ -328,40 +331,80 @@ impl MockTransaction {
         self
     }
 
+    /// Transaction request of the MockTransaction, typed according to its `transaction_type`:
+    /// 0 for legacy, 1 for EIP-2930 and 2 for EIP-1559. Panics on any other type, which can not
+    /// be signed by the mock.
+    pub fn typed_tx(&self) -> TypedTransaction {
+        match self.transaction_type.as_u64() {
+            2 => {
+                let tx = Eip1559TransactionRequest::new()
+                    .from(self.from.address())
+                    .nonce(self.nonce)
+                    .value(self.value)
+                    .data(self.input.clone())
+                    .gas(self.gas)
+                    .chain_id(self.chain_id)
+                    .max_fee_per_gas(self.max_fee_per_gas)
+                    .max_priority_fee_per_gas(self.max_priority_fee_per_gas)
+                    .access_list(self.access_list.clone());
+                let tx = if let Some(to_addr) = self.to.clone() {
+                    tx.to(to_addr.address())
+                } else {
+                    tx
+                };
+                tx.into()
+            }
+            transaction_type @ (0 | 1) => {
+                let tx = TransactionRequest::new()
+                    .from(self.from.address())
+                    .nonce(self.nonce)
+                    .value(self.value)
+                    .data(self.input.clone())
+                    .gas(self.gas)
+                    .chain_id(self.chain_id);
+
+                let tx = if let Some(gas_price) = self.gas_price {
+                    tx.gas_price(gas_price)
+                } else {
+                    tx
+                };
+                let tx = if let Some(to_addr) = self.to.clone() {
+                    tx.to(to_addr.address())
+                } else {
+                    tx
+                };
+                if transaction_type == 1 {
+                    Eip2930TransactionRequest::new(tx, self.access_list.clone()).into()
+                } else {
+                    tx.into()
+                }
+            }
+            transaction_type => panic!("unsupported mock transaction type {transaction_type}"),
+        }
+    }
+
     /// Consumes the mutable ref to the MockTransaction returning the structure
     /// by value.
     pub fn build(&mut self) -> Self {
-        let tx = TransactionRequest::new()
-            .from(self.from.address())
-            .nonce(self.nonce)
-            .value(self.value)
-            .data(self.input.clone())
-            .gas(self.gas)
-            .chain_id(self.chain_id);
-
-        let tx = if let Some(gas_price) = self.gas_price {
-            tx.gas_price(gas_price)
-        } else {
-            tx
-        };
-        let tx = if let Some(to_addr) = self.to.clone() {
-            tx.to(to_addr.address())
-        } else {
-            tx
-        };
-
         match (self.v, self.r, self.s) {
             (None, None, None) => {
                 // Compute sig params and set them in case we have a wallet as `from` attr.
                 if self.from.is_wallet() && self.hash.is_none() {
+                    let tx = self.typed_tx();
                     let sig = self
                         .from
                         .as_wallet()
                         .with_chain_id(self.chain_id)
-                        .sign_transaction_sync(&tx.into())
+                        .sign_transaction_sync(&tx)
                         .expect("sign mock tx");
+                    // Typed transactions carry the y parity instead of the EIP-155 v.
+                    let v = if matches!(tx, TypedTransaction::Legacy(_)) {
+                        sig.v
+                    } else {
+                        sig.v - 35 - 2 * self.chain_id
+                    };
                     // Set sig parameters
-                    self.sig_data((sig.v, sig.r, sig.s));
+                    self.sig_data((v, sig.r, sig.s));
                 }
             }
             (Some(_), Some(_), Some(_)) => (),
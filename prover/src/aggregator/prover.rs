@@ -20,10 +20,10 @@ pub struct Prover {
 }
 
 impl Prover {
-    pub fn from_dirs(params_dir: &str, assets_dir: &str) -> Self {
+    pub fn from_dirs(params_dir: &str, assets_dir: &str) -> Result<Self> {
         env::set_var("KECCAK_ROW", AGG_KECCAK_ROW.to_string());
 
-        let inner = common::Prover::from_params_dir(params_dir, &AGG_DEGREES);
+        let inner = common::Prover::from_params_dir(params_dir, &AGG_DEGREES)?;
         let chunk_protocol = force_to_read(assets_dir, &CHUNK_PROTOCOL_FILENAME);
 
         let raw_vk = try_to_read(assets_dir, &AGG_VK_FILENAME);
@@ -35,11 +35,11 @@ impl Prover {
             );
         }
 
-        Self {
+        Ok(Self {
             inner,
             chunk_protocol,
             raw_vk,
-        }
+        })
     }
 
     // Return true if chunk proofs are valid (same protocol), false otherwise.
@@ -6,6 +6,7 @@ use crate::{
     BatchProof,
 };
 use aggregator::CompressionCircuit;
+use anyhow::Result;
 use halo2_proofs::{
     halo2curves::bn256::{Bn256, G1Affine},
     plonk::VerifyingKey,
@@ -35,17 +36,17 @@ impl Verifier {
         }
     }
 
-    pub fn from_dirs(params_dir: &str, assets_dir: &str) -> Self {
+    pub fn from_dirs(params_dir: &str, assets_dir: &str) -> Result<Self> {
         let raw_vk = force_to_read(assets_dir, &AGG_VK_FILENAME);
         let deployment_code = force_to_read(assets_dir, &DEPLOYMENT_CODE_FILENAME);
 
         env::set_var("COMPRESSION_CONFIG", &*LAYER4_CONFIG_PATH);
-        let inner = common::Verifier::from_params_dir(params_dir, *LAYER4_DEGREE, &raw_vk);
+        let inner = common::Verifier::from_params_dir(params_dir, *LAYER4_DEGREE, &raw_vk)?;
 
-        Self {
+        Ok(Self {
             inner,
             deployment_code,
-        }
+        })
     }
 
     pub fn verify_agg_evm_proof(&self, batch_proof: BatchProof) -> bool {
@@ -1,4 +1,5 @@
-use crate::utils::{load_params, param_path_for_degree};
+use crate::utils::{load_params, params_url, read_or_download_params};
+use anyhow::Result;
 use halo2_proofs::{
     halo2curves::bn256::{Bn256, G1Affine},
     plonk::ProvingKey,
@@ -30,24 +31,25 @@ impl Prover {
         }
     }
 
-    pub fn from_params_dir(params_dir: &str, degrees: &[u32]) -> Self {
+    pub fn from_params_dir(params_dir: &str, degrees: &[u32]) -> Result<Self> {
         let degrees = BTreeSet::from_iter(degrees);
         let max_degree = **degrees.last().unwrap();
 
         // Downsize params if any params of degree doesn't exist.
         let mut params_map = BTreeMap::new();
         for d in BTreeSet::from_iter(degrees).into_iter().rev() {
-            let params = match load_params(params_dir, *d, None) {
+            // Only the params of the max degree are downloaded, the others can be downsized.
+            let params = if *d == max_degree {
+                Ok(read_or_download_params(params_dir, *d, &params_url())?)
+            } else {
+                load_params(params_dir, *d, None)
+            };
+            let params = match params {
                 Ok(params) => params,
                 Err(_) => {
                     let params: &ParamsKZG<_> = params_map
                         .first_key_value()
-                        .unwrap_or_else(|| {
-                            panic!(
-                                "File `{}` must exist",
-                                param_path_for_degree(params_dir, max_degree)
-                            )
-                        })
+                        .expect("params of the max degree are loaded first")
                         .1;
 
                     let mut params: ParamsKZG<_> = params.clone();
@@ -62,9 +64,9 @@ impl Prover {
             params_map.insert(*d, params);
         }
 
-        Self {
+        Ok(Self {
             params_map,
             pk_map: HashMap::new(),
-        }
+        })
     }
 }
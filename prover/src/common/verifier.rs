@@ -1,4 +1,9 @@
-use crate::{io::deserialize_vk, utils::load_params, Proof};
+use crate::{
+    io::deserialize_vk,
+    utils::{params_url, read_or_download_params},
+    Proof,
+};
+use anyhow::Result;
 use halo2_proofs::{
     halo2curves::bn256::{Bn256, Fr, G1Affine},
     plonk::VerifyingKey,
@@ -32,10 +37,10 @@ impl<C: CircuitExt<Fr>> Verifier<C> {
         Self::new(params, vk)
     }
 
-    pub fn from_params_dir(params_dir: &str, degree: u32, vk: &[u8]) -> Self {
-        let params = load_params(params_dir, degree, None).unwrap();
+    pub fn from_params_dir(params_dir: &str, degree: u32, vk: &[u8]) -> Result<Self> {
+        let params = read_or_download_params(params_dir, degree, &params_url())?;
 
-        Self::from_params(params, vk)
+        Ok(Self::from_params(params, vk))
     }
 
     pub fn verify_proof(&self, proof: Proof) -> bool {
@@ -29,8 +29,8 @@ impl<C: TargetCircuit> From<common::Prover> for Prover<C> {
 }
 
 impl<C: TargetCircuit> Prover<C> {
-    pub fn from_params_dir(params_dir: &str) -> Self {
-        common::Prover::from_params_dir(params_dir, &[*INNER_DEGREE]).into()
+    pub fn from_params_dir(params_dir: &str) -> Result<Self> {
+        Ok(common::Prover::from_params_dir(params_dir, &[*INNER_DEGREE])?.into())
     }
 
     pub fn load_or_gen_inner_proof(
@@ -12,7 +12,8 @@ static BATCH_PROVER: LazyLock<Mutex<Prover>> = LazyLock::new(|| {
     let assets_dir = read_env_var("SCROLL_PROVER_ASSETS_DIR", "./test_assets".to_string());
     let params_dir = read_env_var("SCROLL_PROVER_PARAMS_DIR", "./test_params".to_string());
 
-    let prover = Prover::from_dirs(&params_dir, &assets_dir);
+    let prover =
+        Prover::from_dirs(&params_dir, &assets_dir).expect("Failed to construct batch-prover");
     log::info!("Constructed batch-prover");
 
     Mutex::new(prover)
@@ -11,7 +11,8 @@ use std::{
 
 static CHUNK_PROVER: LazyLock<Mutex<Prover>> = LazyLock::new(|| {
     let params_dir = read_env_var("SCROLL_PROVER_PARAMS_DIR", "./test_params".to_string());
-    let prover = Prover::from_params_dir(&params_dir, &ZKEVM_DEGREES);
+    let prover = Prover::from_params_dir(&params_dir, &ZKEVM_DEGREES)
+        .expect("Failed to construct chunk-prover");
     log::info!("Constructed chunk-prover");
 
     Mutex::new(prover)
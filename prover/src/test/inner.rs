@@ -9,7 +9,8 @@ use std::sync::{LazyLock, Mutex};
 
 static INNER_PROVER: LazyLock<Mutex<Prover>> = LazyLock::new(|| {
     let params_dir = read_env_var("SCROLL_PROVER_PARAMS_DIR", "./test_params".to_string());
-    let prover = Prover::from_params_dir(&params_dir, &[*INNER_DEGREE]);
+    let prover = Prover::from_params_dir(&params_dir, &[*INNER_DEGREE])
+        .expect("Failed to construct inner-prover");
     log::info!("Constructed inner-prover");
 
     Mutex::new(prover)
@@ -2,7 +2,7 @@ use crate::{
     types::BlockTraceJsonRpcResult,
     zkevm::circuit::{block_traces_to_witness_block, check_batch_capacity},
 };
-use anyhow::{bail, Result};
+use anyhow::{anyhow, bail, Result};
 use chrono::Utc;
 use eth_types::{l2_types::BlockTrace, Address};
 use git_version::git_version;
@@ -21,9 +21,11 @@ use log4rs::{
 };
 use rand::{Rng, SeedableRng};
 use rand_xorshift::XorShiftRng;
+use sha2::{Digest, Sha256};
 use std::{
+    collections::HashMap,
     fs::{self, metadata, File},
-    io::{BufReader, Read},
+    io::{self, BufReader, Read},
     path::{Path, PathBuf},
     str::FromStr,
     sync::Once,
@@ -85,6 +87,86 @@ pub fn load_params(
     Ok(p)
 }
 
+/// Default base url of the published setup params, as fetched by `testool/download_setup.sh`.
+pub const DEFAULT_PARAMS_URL: &str = "https://circuit-release.s3.us-west-2.amazonaws.com/setup";
+
+/// Manifest of the known sha256 of the params files in a params directory, in the format of
+/// `sha256sum` (`<hex digest>  params<degree>`).
+pub const PARAMS_SHA256_MANIFEST: &str = "params.sha256";
+
+/// Read the known sha256 of each params file, by file name, from the manifest of `params_dir`.
+fn known_params_sha256(params_dir: &str) -> Result<HashMap<String, String>> {
+    let manifest = Path::new(params_dir).join(PARAMS_SHA256_MANIFEST);
+    let content = fs::read_to_string(&manifest)
+        .map_err(|e| anyhow!("failed to read params manifest {}: {e}", manifest.display()))?;
+
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(
+            |line| match line.split_whitespace().collect::<Vec<_>>()[..] {
+                [digest, file_name] => Ok((file_name.to_string(), digest.to_lowercase())),
+                _ => bail!(
+                    "invalid line in params manifest {}: {line}",
+                    manifest.display()
+                ),
+            },
+        )
+        .collect()
+}
+
+/// Load setup params of `degree` from the `params_dir` directory, downloading them first from
+/// `{url_base}/params{degree}` if they are not cached there yet.
+///
+/// A downloaded file is only moved into the cache once its sha256 matches the one listed for it
+/// in the [`PARAMS_SHA256_MANIFEST`] of `params_dir`, so a missing manifest entry fails the
+/// download. The loaded params are checked by [`load_params`] in any case.
+pub fn read_or_download_params(
+    params_dir: &str,
+    degree: u32,
+    url_base: &str,
+) -> Result<ParamsKZG<Bn256>> {
+    let params_path = param_path_for_degree(params_dir, degree);
+    if !Path::new(&params_path).exists() {
+        let file_name = format!("params{degree}");
+        let expected = known_params_sha256(params_dir)?
+            .remove(&file_name)
+            .ok_or_else(|| anyhow!("no known sha256 of {file_name} in {PARAMS_SHA256_MANIFEST}"))?;
+
+        let url = format!("{}/{file_name}", url_base.trim_end_matches('/'));
+        let tmp_path = format!("{params_path}.download");
+        log::info!("Downloading params of degree {degree} from {url}");
+        if let Err(e) = download(&url, &tmp_path) {
+            let _ = fs::remove_file(&tmp_path);
+            bail!("failed to download params of degree {degree} from {url}: {e}");
+        }
+
+        let mut hasher = Sha256::new();
+        io::copy(&mut File::open(&tmp_path)?, &mut hasher)?;
+        let digest = hex::encode(hasher.finalize());
+        if digest != expected {
+            fs::remove_file(&tmp_path)?;
+            bail!("params of degree {degree} have sha256 {digest}, expected {expected}");
+        }
+        fs::rename(&tmp_path, &params_path)?;
+    }
+
+    load_params(params_dir, degree, None)
+}
+
+fn download(url: &str, path: &str) -> Result<()> {
+    // The params of high degrees take several GB, don't time out the transfer.
+    let client = reqwest::blocking::Client::builder().timeout(None).build()?;
+    let mut response = client.get(url).send()?.error_for_status()?;
+    response.copy_to(&mut File::create(path)?)?;
+    Ok(())
+}
+
+/// Base url of the setup params to download, set by `SCROLL_PROVER_PARAMS_URL`.
+pub fn params_url() -> String {
+    read_env_var("SCROLL_PROVER_PARAMS_URL", DEFAULT_PARAMS_URL.to_string())
+}
+
 /// get a block-result from file
 pub fn get_block_trace_from_file<P: AsRef<Path>>(path: P) -> BlockTrace {
     let mut buffer = Vec::new();
@@ -18,8 +18,8 @@ pub struct Prover {
 }
 
 impl Prover {
-    pub fn from_dirs(params_dir: &str, assets_dir: &str) -> Self {
-        let inner = common::Prover::from_params_dir(params_dir, &ZKEVM_DEGREES);
+    pub fn from_dirs(params_dir: &str, assets_dir: &str) -> Result<Self> {
+        let inner = common::Prover::from_params_dir(params_dir, &ZKEVM_DEGREES)?;
 
         let raw_vk = try_to_read(assets_dir, &CHUNK_VK_FILENAME);
         if raw_vk.is_none() {
@@ -30,7 +30,7 @@ impl Prover {
             );
         }
 
-        Self { inner, raw_vk }
+        Ok(Self { inner, raw_vk })
     }
 
     pub fn get_vk(&self) -> Option<Vec<u8>> {
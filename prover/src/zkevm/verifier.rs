@@ -6,6 +6,7 @@ use crate::{
     ChunkProof,
 };
 use aggregator::CompressionCircuit;
+use anyhow::Result;
 use halo2_proofs::{
     halo2curves::bn256::{Bn256, G1Affine},
     plonk::VerifyingKey,
@@ -30,11 +31,11 @@ impl Verifier {
         common::Verifier::new(params, vk).into()
     }
 
-    pub fn from_dirs(params_dir: &str, assets_dir: &str) -> Self {
+    pub fn from_dirs(params_dir: &str, assets_dir: &str) -> Result<Self> {
         let raw_vk = force_to_read(assets_dir, &CHUNK_VK_FILENAME);
 
         env::set_var("COMPRESSION_CONFIG", &*LAYER2_CONFIG_PATH);
-        common::Verifier::from_params_dir(params_dir, *LAYER2_DEGREE, &raw_vk).into()
+        Ok(common::Verifier::from_params_dir(params_dir, *LAYER2_DEGREE, &raw_vk)?.into())
     }
 
     pub fn verify_chunk_proof(&self, proof: ChunkProof) -> bool {
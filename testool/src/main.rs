@@ -25,6 +25,7 @@ use std::{
     time::SystemTime,
 };
 use strum_macros::EnumString;
+use zkevm_circuits::evm_circuit::coverage::ExecutionStateCoverage;
 
 const REPORT_FOLDER: &str = "report";
 const CODEHASH_FILE: &str = "./codehash.txt";
@@ -88,6 +89,10 @@ struct Args {
     #[clap(long)]
     exclude_test_ids: Option<String>,
 
+    /// Execution state coverage file generated by the zkevm-circuits coverage test
+    #[clap(long)]
+    coverage: Option<PathBuf>,
+
     /// Verbose
     #[clap(short, long)]
     v: bool,
@@ -131,6 +136,42 @@ fn write_test_ids(test_ids: &[String]) -> Result<()> {
     Ok(())
 }
 
+fn check_execution_state_coverage(path: &PathBuf) -> Result<()> {
+    let coverage = ExecutionStateCoverage::read_json(path)?;
+    for opcode in coverage.uncovered() {
+        log::warn!(
+            "opcode 0x{:02x} ({}) has no execution state",
+            opcode.opcode,
+            opcode.name
+        );
+    }
+    for opcode in coverage.ambiguous() {
+        log::warn!(
+            "opcode 0x{:02x} ({}) is claimed by {:?}",
+            opcode.opcode,
+            opcode.name,
+            opcode.execution_states
+        );
+    }
+    if coverage
+        .uncovered()
+        .chain(coverage.ambiguous())
+        .next()
+        .is_some()
+    {
+        bail!(
+            "execution state coverage in {} is incomplete",
+            path.display()
+        );
+    }
+    info!(
+        "execution state coverage loaded from {}: {} opcodes covered",
+        path.display(),
+        coverage.opcodes.len()
+    );
+    Ok(())
+}
+
 fn run_single_test(test: StateTest, circuits_config: CircuitsConfig) -> Result<()> {
     log::info!("run single test {}", &test);
     let circuits_config = CircuitsConfig {
@@ -167,6 +208,10 @@ fn go() -> Result<()> {
 
     env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
 
+    if let Some(coverage) = &args.coverage {
+        check_execution_state_coverage(coverage)?;
+    }
+
     log::info!("Using suite '{}'", args.suite);
     log::info!("Parsing and compliling tests...");
     let compiler = Compiler::new(true, Some(PathBuf::from(CODEHASH_FILE)))?;
@@ -601,9 +601,13 @@ pub fn run_test(
     //builder.sdb.list_accounts();
 
     let check_ccc = || {
-        let row_usage = ScrollSuperCircuit::min_num_rows_block_subcircuits(&witness_block);
+        let row_usage = ScrollSuperCircuit::row_usage(&witness_block);
         let mut overflow = false;
-        for (num, limit) in row_usage.iter().zip_eq(get_sub_circuit_limit_l2().iter()) {
+        for (num, limit) in row_usage
+            .sub_circuits
+            .iter()
+            .zip_eq(get_sub_circuit_limit_l2().iter())
+        {
             if num.row_num_real > *limit {
                 log::warn!(
                     "ccc detail: suite.id {}, st.id {}, circuit {}, num {}, limit {}",
@@ -616,7 +620,7 @@ pub fn run_test(
                 overflow = true;
             }
         }
-        let max_row_usage = row_usage.iter().max_by_key(|r| r.row_num_real).unwrap();
+        let max_row_usage = row_usage.bottleneck().unwrap();
         if overflow {
             log::warn!(
                 "ccc overflow: st.id {}, detail {} {}",
@@ -18,6 +18,9 @@ use halo2_proofs::{
 };
 use std::vec;
 
+#[cfg(feature = "dual-codehash")]
+use crate::util::keccak;
+
 use super::{
     bytecode_unroller::{unroll_with_codehash, BytecodeRow, UnrolledBytecode},
     param::PUSH_TABLE_WIDTH,
@@ -462,7 +465,52 @@ impl<F: Field> SubCircuitConfig<F> for BytecodeCircuitConfig<F> {
                 is_byte_to_header(meta),
             ]))
         });
-        #[cfg(not(feature = "poseidon-codehash"))]
+        // In the dual code hash mode, code_hash is the poseidon code hash and the keccak code hash
+        // of the bytecode is kept in its own column.
+        // When is_header_to_header or q_last ->
+        // assert cur.keccak_code_hash == EMPTY_HASH
+        // When next.tag == Byte ->
+        // assert next.keccak_code_hash == cur.keccak_code_hash
+        #[cfg(feature = "dual-codehash")]
+        meta.create_gate("keccak code hash", |meta| {
+            let mut cb = BaseConstraintBuilder::default();
+
+            let keccak_code_hash = bytecode_table.keccak_code_hash;
+            cb.condition(
+                or::expr(vec![
+                    is_header_to_header(meta),
+                    meta.query_fixed(q_last, Rotation::cur()),
+                ]),
+                |cb| {
+                    cb.require_equal(
+                        "assert cur.keccak_code_hash == EMPTY_HASH",
+                        meta.query_advice(keccak_code_hash, Rotation::cur()),
+                        rlc::expr(
+                            &EMPTY_CODE_HASH_LE.map(|v| Expression::Constant(F::from(v as u64))),
+                            challenges.evm_word(),
+                        ),
+                    );
+                },
+            );
+
+            cb.condition(
+                and::expr(vec![
+                    not::expr(meta.query_fixed(q_last, Rotation::cur())),
+                    meta.query_advice(bytecode_table.tag, Rotation::next()),
+                ]),
+                |cb| {
+                    cb.require_equal(
+                        "next.keccak_code_hash == cur.keccak_code_hash",
+                        meta.query_advice(keccak_code_hash, Rotation::next()),
+                        meta.query_advice(keccak_code_hash, Rotation::cur()),
+                    );
+                },
+            );
+
+            cb.gate(meta.query_fixed(q_enable, Rotation::cur()))
+        });
+
+        #[cfg(any(not(feature = "poseidon-codehash"), feature = "dual-codehash"))]
         meta.lookup_any(
             "keccak256_table_lookup(cur.value_rlc, cur.length, cur.hash)",
             |meta| {
@@ -478,8 +526,12 @@ impl<F: Field> SubCircuitConfig<F> for BytecodeCircuitConfig<F> {
 
                 let mut constraints = vec![(enable.clone(), keccak_enable)];
 
+                #[cfg(feature = "dual-codehash")]
+                let keccak_code_hash = bytecode_table.keccak_code_hash;
+                #[cfg(not(feature = "dual-codehash"))]
+                let keccak_code_hash = bytecode_table.code_hash;
                 for (circuit_column, table_column) in
-                    keccak_table.match_columns(value_rlc, length, bytecode_table.code_hash)
+                    keccak_table.match_columns(value_rlc, length, keccak_code_hash)
                 {
                     constraints.push((
                         enable.clone() * meta.query_advice(circuit_column, Rotation::cur()),
@@ -557,6 +609,9 @@ impl<F: Field> BytecodeCircuitConfig<F> {
             }
         });
 
+        #[cfg(feature = "dual-codehash")]
+        let empty_keccak_code_hash = Self::empty_keccak_code_hash(challenges);
+
         let mut is_first_time = true;
         layouter.assign_region(
             || "assign bytecode",
@@ -601,6 +656,8 @@ impl<F: Field> BytecodeCircuitConfig<F> {
                         idx,
                         last_row_offset,
                     )?;
+                    #[cfg(feature = "dual-codehash")]
+                    self.assign_keccak_code_hash(&mut region, idx, empty_keccak_code_hash)?;
                 }
 
                 self.assign_overwrite(&mut region, overwrite, challenges)?;
@@ -609,6 +666,30 @@ impl<F: Field> BytecodeCircuitConfig<F> {
         )
     }
 
+    /// The keccak code hash of the padding rows and empty bytecodes.
+    #[cfg(feature = "dual-codehash")]
+    pub(crate) fn empty_keccak_code_hash(challenges: &Challenges<Value<F>>) -> Value<F> {
+        challenges
+            .evm_word()
+            .map(|challenge| rlc::value(EMPTY_CODE_HASH_LE.as_ref(), challenge))
+    }
+
+    #[cfg(feature = "dual-codehash")]
+    pub(crate) fn assign_keccak_code_hash(
+        &self,
+        region: &mut Region<'_, F>,
+        offset: usize,
+        keccak_code_hash: Value<F>,
+    ) -> Result<(), Error> {
+        region.assign_advice(
+            || format!("assign keccak_code_hash {offset}"),
+            self.bytecode_table.keccak_code_hash,
+            offset,
+            || keccak_code_hash,
+        )?;
+        Ok(())
+    }
+
     fn assign_overwrite(
         &self,
         region: &mut Region<'_, F>,
@@ -693,6 +774,13 @@ impl<F: Field> BytecodeCircuitConfig<F> {
                 rlc::value(&bytecode.rows[0].code_hash.to_le_bytes(), challenge)
             }
         });
+        #[cfg(feature = "dual-codehash")]
+        let keccak_code_hash = {
+            let keccak_hash = keccak(&bytecode.bytes);
+            challenges
+                .evm_word()
+                .map(|challenge| rlc::value(&keccak_hash.to_le_bytes(), challenge))
+        };
 
         for (idx, row) in bytecode.rows.iter().enumerate() {
             if fail_fast && *offset > last_row_offset {
@@ -756,6 +844,8 @@ impl<F: Field> BytecodeCircuitConfig<F> {
                     length,
                     F::from(push_data_size),
                 )?;
+                #[cfg(feature = "dual-codehash")]
+                self.assign_keccak_code_hash(region, *offset, keccak_code_hash)?;
                 /*
                 trace!(
                     "bytecode.set_row({}): last:{} h:{:?} t:{:?} i:{:?} c:{:?} v:{:?} pdl:{} rlc:{:?} l:{:?} pds:{:?}",
@@ -946,6 +1036,11 @@ impl<F: Field> BytecodeCircuitConfig<F> {
         region.name_column(|| "BYTECODE_push_data_size", self.push_data_size);
         region.name_column(|| "BYTECODE_push_acc", self.push_acc);
         region.name_column(|| "BYTECODE_value_rlc", self.value_rlc);
+        #[cfg(feature = "dual-codehash")]
+        region.name_column(
+            || "BYTECODE_keccak_code_hash",
+            self.bytecode_table.keccak_code_hash,
+        );
         region.name_column(|| "BYTECODE_push_data_left_inv", self.push_data_left_inv);
         region.name_column(
             || "BYTECODE_index_length_diff_inv",
@@ -435,6 +435,8 @@ impl<F: Field, const BYTES_IN_FIELD: usize> ToHashBlockCircuitConfig<F, BYTES_IN
         );
 
         let empty_hash = Value::known(POSEIDON_CODE_HASH_EMPTY.to_word().to_scalar().unwrap());
+        #[cfg(feature = "dual-codehash")]
+        let empty_keccak_code_hash = BytecodeCircuitConfig::empty_keccak_code_hash(challenges);
 
         layouter.assign_region(
             || "assign bytecode with poseidon hash extension",
@@ -482,6 +484,8 @@ impl<F: Field, const BYTES_IN_FIELD: usize> ToHashBlockCircuitConfig<F, BYTES_IN
                         idx,
                         last_row_offset,
                     )?;
+                    #[cfg(feature = "dual-codehash")]
+                    base_conf.assign_keccak_code_hash(&mut region, idx, empty_keccak_code_hash)?;
                     self.set_header_row(&mut region, 0, idx)?;
                 }
 
@@ -4,10 +4,11 @@ use crate::{
     table::BytecodeFieldTag,
     util::{is_push_with_data, keccak, unusable_rows, Challenges, SubCircuit},
 };
-use bus_mapping::{evm::OpcodeId, state_db::CodeDB};
-use eth_types::{Bytecode, Field, ToWord, Word};
+use bus_mapping::{evm::OpcodeId, mock::BlockData, state_db::CodeDB};
+use eth_types::{bytecode, geth_types::GethData, Bytecode, Field, ToWord, Word};
 use halo2_proofs::{dev::MockProver, halo2curves::bn256::Fr};
 use log::error;
+use mock::{eth, TestContext, MOCK_ACCOUNTS};
 
 #[test]
 fn bytecode_circuit_unusable_rows() {
@@ -163,6 +164,50 @@ fn bytecode_incomplete() {
     test_bytecode_circuit_unrolled::<Fr>(k, vec![unroll(vec![7u8; 2usize.pow(k) + 1])], false);
 }
 
+/// Tests that accounts sharing the same code have it assigned only once
+#[test]
+fn bytecode_shared_by_accounts() {
+    let k = 9;
+    // A proxy-like code deployed at three addresses
+    let code = bytecode! {
+        PUSH1(0)
+        CALLDATALOAD
+        POP
+        STOP
+    };
+    let ctx = TestContext::<4, 1>::new(
+        None,
+        |accs| {
+            accs[0].address(MOCK_ACCOUNTS[0]).balance(eth(10));
+            accs[1].address(MOCK_ACCOUNTS[1]).code(code.clone());
+            accs[2].address(MOCK_ACCOUNTS[2]).code(code.clone());
+            accs[3].address(MOCK_ACCOUNTS[3]).code(code.clone());
+        },
+        |mut txs, accs| {
+            txs[0].from(accs[0].address).to(accs[1].address);
+        },
+        |block, _tx| block,
+    )
+    .unwrap();
+    let block: GethData = ctx.into();
+    let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+    builder
+        .handle_block(&block.eth_block, &block.geth_traces)
+        .unwrap();
+    let block = crate::witness::block_convert::<Fr>(&builder.block, &builder.code_db).unwrap();
+
+    // The shared code and the empty code
+    assert_eq!(block.bytecodes.len(), 2);
+    assert_eq!(
+        BytecodeCircuit::<Fr>::min_num_rows_block(&block).0,
+        code.to_vec().len() + 2
+    );
+
+    let circuit = BytecodeCircuit::<Fr>::new_from_block_sized(&block, 2usize.pow(k));
+    let prover = MockProver::<Fr>::run(k, &circuit, Vec::new()).unwrap();
+    prover.assert_satisfied_par();
+}
+
 /// Tests multiple bytecodes in a single circuit
 #[test]
 fn bytecode_push() {
@@ -329,6 +329,39 @@ fn gen_create_data() -> CircuitInputBuilder {
     builder
 }
 
+fn gen_create_tx_data() -> CircuitInputBuilder {
+    let init_code = bytecode! {
+        PUSH32(word!("3000000000000000000000000000000000000000000000000000000000000000"))
+        PUSH1(0)
+        MSTORE
+        PUSH1(2)
+        PUSH1(0)
+        RETURN
+    };
+
+    let test_ctx = TestContext::<1, 1>::new(
+        None,
+        |accs| {
+            accs[0].address(MOCK_ACCOUNTS[0]).balance(eth(20));
+        },
+        |mut txs, _accs| {
+            txs[0]
+                .from(MOCK_ACCOUNTS[0])
+                .gas_price(gwei(2))
+                .gas(Word::from(0x10000))
+                .input(init_code.into());
+        },
+        |block, _tx| block.number(0xcafeu64),
+    )
+    .unwrap();
+    let block: GethData = test_ctx.into();
+    let mut builder = BlockData::new_from_geth_data(block.clone()).new_circuit_input_builder();
+    builder
+        .handle_block(&block.eth_block, &block.geth_traces)
+        .unwrap();
+    builder
+}
+
 fn gen_return_data() -> CircuitInputBuilder {
     let code = bytecode! {
         PUSH21(Word::from("6B6020600060003760206000F3600052600C6014F3"))
@@ -404,6 +437,16 @@ fn copy_circuit_valid_create() {
     assert_eq!(test_copy_circuit_from_block(block), Ok(()));
 }
 
+#[test]
+fn copy_circuit_valid_create_tx() {
+    let builder = gen_create_tx_data();
+    assert!(builder.block.copy_events.iter().any(|event| {
+        event.src_type == CopyDataType::TxCalldata && event.dst_type == CopyDataType::Bytecode
+    }));
+    let block = block_convert::<Fr>(&builder.block, &builder.code_db).unwrap();
+    assert_eq!(test_copy_circuit_from_block(block), Ok(()));
+}
+
 #[test]
 fn copy_circuit_valid_return() {
     let builder = gen_return_data();
@@ -6,6 +6,7 @@ use halo2_proofs::{
     plonk::*,
 };
 
+pub mod coverage;
 mod execution;
 pub mod param;
 pub(crate) mod step;
@@ -0,0 +1,138 @@
+//! Opcode coverage report of the EVM circuit execution states.
+//!
+//! Every opcode byte is expected to be handled by exactly one [`ExecutionState`] in the success
+//! path (or by `ErrorInvalidOpcode` for undefined bytes). The report generated here is written as
+//! JSON and consumed by testool to annotate its results with the circuit support of each opcode.
+
+use super::ExecutionState;
+use bus_mapping::evm::OpcodeId;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs::File, io, path::Path};
+use strum::IntoEnumIterator;
+
+/// Execution states that claim an opcode for a specific stack pointer only. They are reported
+/// separately, since they overlap with the success state of the same opcode by design.
+const STACK_POINTER_STATES: [ExecutionState; 1] = [ExecutionState::ErrorStack];
+
+/// Coverage of a single opcode byte.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OpcodeCoverage {
+    /// The opcode byte.
+    pub opcode: u8,
+    /// Mnemonic of the opcode.
+    pub name: String,
+    /// Execution states claiming responsibility for the opcode.
+    pub execution_states: Vec<String>,
+}
+
+impl OpcodeCoverage {
+    /// Whether the opcode is claimed by exactly one execution state.
+    pub fn is_unique(&self) -> bool {
+        self.execution_states.len() == 1
+    }
+}
+
+/// Coverage report mapping every opcode byte to the execution states handling it.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionStateCoverage {
+    /// Per opcode byte coverage, ordered by opcode byte.
+    pub opcodes: Vec<OpcodeCoverage>,
+    /// Execution states which do not claim any opcode (internal states, precompiles and the
+    /// errors which are not opcode specific).
+    pub opcode_free_states: Vec<String>,
+}
+
+impl ExecutionStateCoverage {
+    /// Enumerate all execution states and the opcodes they are responsible for.
+    pub fn generate() -> Self {
+        let mut claims: BTreeMap<u8, Vec<String>> =
+            (u8::MIN..=u8::MAX).map(|byte| (byte, vec![])).collect();
+        let mut opcode_free_states = vec![];
+
+        for state in ExecutionState::iter() {
+            if STACK_POINTER_STATES.contains(&state) {
+                continue;
+            }
+            let responsible_ops = state.responsible_opcodes();
+            if responsible_ops.is_empty() {
+                opcode_free_states.push(format!("{state:?}"));
+                continue;
+            }
+            for responsible_op in responsible_ops {
+                claims
+                    .get_mut(&responsible_op.opcode().as_u8())
+                    .expect("all opcode bytes are present")
+                    .push(format!("{state:?}"));
+            }
+        }
+
+        let opcodes = claims
+            .into_iter()
+            .map(|(opcode, execution_states)| OpcodeCoverage {
+                opcode,
+                name: OpcodeId::from(opcode).to_string(),
+                execution_states,
+            })
+            .collect();
+
+        Self {
+            opcodes,
+            opcode_free_states,
+        }
+    }
+
+    /// Opcodes not claimed by any execution state.
+    pub fn uncovered(&self) -> impl Iterator<Item = &OpcodeCoverage> {
+        self.opcodes
+            .iter()
+            .filter(|coverage| coverage.execution_states.is_empty())
+    }
+
+    /// Opcodes claimed by more than one execution state.
+    pub fn ambiguous(&self) -> impl Iterator<Item = &OpcodeCoverage> {
+        self.opcodes
+            .iter()
+            .filter(|coverage| coverage.execution_states.len() > 1)
+    }
+
+    /// Write the report as JSON.
+    pub fn write_json(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let file = File::create(path)?;
+        serde_json::to_writer_pretty(file, self).map_err(io::Error::from)
+    }
+
+    /// Read a report previously written with [`Self::write_json`].
+    pub fn read_json(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = File::open(path)?;
+        serde_json::from_reader(file).map_err(io::Error::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_opcode_maps_to_exactly_one_state() {
+        let coverage = ExecutionStateCoverage::generate();
+        assert_eq!(coverage.opcodes.len(), 256);
+
+        let uncovered = coverage.uncovered().collect::<Vec<_>>();
+        assert!(uncovered.is_empty(), "uncovered opcodes: {uncovered:?}");
+        let ambiguous = coverage.ambiguous().collect::<Vec<_>>();
+        assert!(ambiguous.is_empty(), "ambiguous opcodes: {ambiguous:?}");
+
+        // Emit the machine-readable report for testool when requested.
+        if let Ok(path) = std::env::var("EXECUTION_STATE_COVERAGE_FILE") {
+            coverage.write_json(path).unwrap();
+        }
+    }
+
+    #[test]
+    fn coverage_json_roundtrip() {
+        let coverage = ExecutionStateCoverage::generate();
+        let json = serde_json::to_string(&coverage).unwrap();
+        let decoded: ExecutionStateCoverage = serde_json::from_str(&json).unwrap();
+        assert_eq!(coverage, decoded);
+    }
+}
@@ -107,6 +107,7 @@ mod extcodehash;
 mod extcodesize;
 mod gas;
 mod gasprice;
+mod invalid_tx;
 mod is_zero;
 mod jump;
 mod jumpdest;
@@ -192,6 +193,7 @@ use extcodehash::ExtcodehashGadget;
 use extcodesize::ExtcodesizeGadget;
 use gas::GasGadget;
 use gasprice::GasPriceGadget;
+use invalid_tx::InvalidTxGadget;
 use is_zero::IsZeroGadget;
 use jump::JumpGadget;
 use jumpdest::JumpdestGadget;
@@ -270,6 +272,7 @@ pub(crate) struct ExecutionConfig<F> {
     end_block_gadget: Box<EndBlockGadget<F>>,
     end_inner_block_gadget: Box<EndInnerBlockGadget<F>>,
     end_tx_gadget: Box<EndTxGadget<F>>,
+    invalid_tx_gadget: Box<InvalidTxGadget<F>>,
     // opcode gadgets
     add_sub_gadget: Box<AddSubGadget<F>>,
     addmod_gadget: Box<AddModGadget<F>>,
@@ -428,10 +431,13 @@ impl<F: Field> ExecutionConfig<F> {
 
             // NEW: Enabled, this will break hand crafted tests, maybe we can remove them?
             let first_step_check = {
-                let begin_tx_end_block_selector = step_curr
-                    .execution_state_selector([ExecutionState::BeginTx, ExecutionState::EndBlock]);
+                let begin_tx_end_block_selector = step_curr.execution_state_selector([
+                    ExecutionState::BeginTx,
+                    ExecutionState::InvalidTx,
+                    ExecutionState::EndBlock,
+                ]);
                 iter::once((
-                    "First step should be BeginTx or EndBlock",
+                    "First step should be BeginTx, InvalidTx or EndBlock",
                     q_step_first * (1.expr() - begin_tx_end_block_selector),
                 ))
             };
@@ -551,6 +557,7 @@ impl<F: Field> ExecutionConfig<F> {
             end_block_gadget: configure_gadget!(),
             end_inner_block_gadget: configure_gadget!(),
             end_tx_gadget: configure_gadget!(),
+            invalid_tx_gadget: configure_gadget!(),
             // opcode gadgets
             add_sub_gadget: configure_gadget!(),
             addmod_gadget: configure_gadget!(),
@@ -830,14 +837,19 @@ impl<F: Field> ExecutionConfig<F> {
                 .chain(
                     IntoIterator::into_iter([
                         (
-                            "EndTx can only transit to BeginTx or EndInnerBlock",
+                            "EndTx can only transit to BeginTx, InvalidTx or EndInnerBlock",
                             ExecutionState::EndTx,
-                            vec![ExecutionState::BeginTx, ExecutionState::EndInnerBlock],
+                            vec![ExecutionState::BeginTx, ExecutionState::InvalidTx, ExecutionState::EndInnerBlock],
                         ),
                         (
-                            "EndInnerBlock can only transition to BeginTx, EndInnerBlock or EndBlock",
+                            "InvalidTx can only transit to BeginTx, InvalidTx or EndInnerBlock",
+                            ExecutionState::InvalidTx,
+                            vec![ExecutionState::BeginTx, ExecutionState::InvalidTx, ExecutionState::EndInnerBlock],
+                        ),
+                        (
+                            "EndInnerBlock can only transition to BeginTx, InvalidTx, EndInnerBlock or EndBlock",
                             ExecutionState::EndInnerBlock,
-                            vec![ExecutionState::BeginTx, ExecutionState::EndInnerBlock, ExecutionState::EndBlock],
+                            vec![ExecutionState::BeginTx, ExecutionState::InvalidTx, ExecutionState::EndInnerBlock, ExecutionState::EndBlock],
                         ),
                         (
                             "EndBlock can only transit to EndBlock",
@@ -851,9 +863,14 @@ impl<F: Field> ExecutionConfig<F> {
                 .chain(
                     IntoIterator::into_iter([
                         (
-                            "Only EndTx or EndInnerBlock can transit to BeginTx",
+                            "Only EndTx, InvalidTx or EndInnerBlock can transit to BeginTx",
                             ExecutionState::BeginTx,
-                            vec![ExecutionState::EndTx, ExecutionState::EndInnerBlock],
+                            vec![ExecutionState::EndTx, ExecutionState::InvalidTx, ExecutionState::EndInnerBlock],
+                        ),
+                        (
+                            "Only EndTx, InvalidTx or EndInnerBlock can transit to InvalidTx",
+                            ExecutionState::InvalidTx,
+                            vec![ExecutionState::EndTx, ExecutionState::InvalidTx, ExecutionState::EndInnerBlock],
                         ),
                         (
                             "Only ExecutionState which halts / precompile or BeginTx can transit to EndTx",
@@ -871,9 +888,9 @@ impl<F: Field> ExecutionConfig<F> {
                         ),
                         (
                             // Empty block can result multiple EndInnerBlock states.
-                            "Only EndTx or EndInnerBlock can transit to EndInnerBlock",
+                            "Only EndTx, InvalidTx or EndInnerBlock can transit to EndInnerBlock",
                             ExecutionState::EndInnerBlock,
-                            vec![ExecutionState::EndTx, ExecutionState::EndInnerBlock],
+                            vec![ExecutionState::EndTx, ExecutionState::InvalidTx, ExecutionState::EndInnerBlock],
                         ),
                     ])
                     .filter(move |(_, _, from)| !from.contains(&execution_state))
@@ -882,9 +899,9 @@ impl<F: Field> ExecutionConfig<F> {
                 .chain(
                     IntoIterator::into_iter([
                         (
-                            "EndInnerBlock -> BeginTx/EndInnerBlock: block number increases by one",
+                            "EndInnerBlock -> BeginTx/InvalidTx/EndInnerBlock: block number increases by one",
                             ExecutionState::EndInnerBlock,
-                            vec![ExecutionState::BeginTx, ExecutionState::EndInnerBlock],
+                            vec![ExecutionState::BeginTx, ExecutionState::InvalidTx, ExecutionState::EndInnerBlock],
                             step_next.state.block_number.expr() - step_curr.state.block_number.expr() - 1.expr(),
                         ),
                         (
@@ -945,7 +962,8 @@ impl<F: Field> ExecutionConfig<F> {
             if let CellType::Lookup(table) = column.cell_type {
                 let name = format!("{table:?}");
                 meta.lookup_any(Box::leak(name.into_boxed_str()), |meta| {
-                    let table_expressions = match table {
+                    #[allow(unused_mut)]
+                    let mut table_expressions = match table {
                         Table::Fixed => fixed_table,
                         Table::Tx => tx_table,
                         Table::Rw => rw_table,
@@ -961,6 +979,12 @@ impl<F: Field> ExecutionConfig<F> {
                         Table::PowOfRand => pow_of_rand_table,
                     }
                     .table_exprs(meta);
+                    // The EVM circuit looks up bytecodes by the code hash it executes, so it
+                    // leaves out the keccak code hash, which is the last bytecode table column.
+                    #[cfg(feature = "dual-codehash")]
+                    if table == Table::Bytecode {
+                        table_expressions.pop();
+                    }
                     vec![(
                         column.expr(),
                         rlc::expr(&table_expressions, challenges.lookup_input()),
@@ -1536,6 +1560,7 @@ impl<F: Field> ExecutionConfig<F> {
             // internal states
             ExecutionState::BeginTx => assign_exec_step!(self.begin_tx_gadget),
             ExecutionState::EndTx => assign_exec_step!(self.end_tx_gadget),
+            ExecutionState::InvalidTx => assign_exec_step!(self.invalid_tx_gadget),
             ExecutionState::EndInnerBlock => assign_exec_step!(self.end_inner_block_gadget),
             ExecutionState::EndBlock => assign_exec_step!(self.end_block_gadget),
             // opcode
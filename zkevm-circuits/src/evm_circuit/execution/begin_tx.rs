@@ -1,13 +1,13 @@
 use crate::{
     evm_circuit::{
         execution::ExecutionGadget,
-        param::{N_BYTES_ACCOUNT_ADDRESS, N_BYTES_GAS, N_BYTES_U64, N_BYTES_WORD},
+        param::{N_BYTES_ACCOUNT_ADDRESS, N_BYTES_GAS, N_BYTES_WORD},
         step::ExecutionState,
         util::{
             and,
             common_gadget::{
                 TransferGadgetInfo, TransferWithGasFeeGadget, TxAccessListGadget, TxEip1559Gadget,
-                TxL1FeeGadget, TxL1MsgGadget,
+                TxIntrinsicGasGadget, TxL1FeeGadget, TxL1MsgGadget,
             },
             constraint_builder::{
                 ConstrainBuilderCommon, EVMConstraintBuilder, ReversionInfo, StepStateTransition,
@@ -15,8 +15,8 @@ use crate::{
             },
             from_bytes,
             math_gadget::{
-                ConstantDivisionGadget, ContractCreateGadget, IsEqualGadget, IsZeroGadget,
-                LtGadget, MulWordByU64Gadget, RangeCheckGadget,
+                ContractCreateGadget, IsEqualGadget, IsZeroGadget, LtGadget, MulWordByU64Gadget,
+                RangeCheckGadget,
             },
             precompile_gadget::PrecompileGadget,
             CachedRegion, Cell, StepRws, Word,
@@ -65,7 +65,7 @@ pub(crate) struct BeginTxGadget<F> {
     tx_value: Word<F>,
     tx_call_data_length: Cell<F>,
     is_call_data_empty: IsZeroGadget<F>,
-    tx_call_data_word_length: ConstantDivisionGadget<F, N_BYTES_U64>,
+    tx_intrinsic_gas: TxIntrinsicGasGadget<F>,
     tx_call_data_gas_cost: Cell<F>,
     // The gas cost for rlp-encoded bytes of unsigned tx
     tx_data_gas_cost: Cell<F>,
@@ -245,35 +245,23 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
         ]);
         let precompile_input_len = cb.query_cell();
 
-        let tx_call_data_word_length =
-            ConstantDivisionGadget::construct(cb, tx_call_data_length.expr() + 31.expr(), 32);
+        let tx_intrinsic_gas = TxIntrinsicGasGadget::construct(
+            cb,
+            tx_is_create.expr(),
+            tx_call_data_length.expr(),
+            tx_call_data_gas_cost.expr(),
+            tx_access_list.gas_cost(),
+        );
 
         // Use intrinsic gas
         // TODO2: contrain calling precompile directly
 
         let intrinsic_gas_cost = cb.query_cell();
         cb.condition(not::expr(is_precompile.expr()), |cb| {
-            // Calculate gas cost of init code only for EIP-3860 of Shanghai.
-            #[cfg(feature = "shanghai")]
-            let init_code_gas_cost = select::expr(
-                tx_is_create.expr(),
-                tx_call_data_word_length.quotient().expr()
-                    * eth_types::evm_types::INIT_CODE_WORD_GAS.expr(),
-                0.expr(),
-            );
-            #[cfg(not(feature = "shanghai"))]
-            let init_code_gas_cost = 0.expr();
-
             cb.require_equal(
                 "calculate intrinsic gas cost",
                 intrinsic_gas_cost.expr(),
-                select::expr(
-                    tx_is_create.expr(),
-                    eth_types::evm_types::GasCost::CREATION_TX.expr(),
-                    eth_types::evm_types::GasCost::TX.expr(),
-                ) + tx_call_data_gas_cost.expr()
-                    + tx_access_list.gas_cost()
-                    + init_code_gas_cost,
+                tx_intrinsic_gas.gas_cost(),
             )
         });
         // Check gas_left is sufficient
@@ -365,6 +353,7 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
             tx_id.expr(),
             tx_type.expr(),
             tx_gas.expr(),
+            &tx_gas_price,
             tx_l1_fee.tx_l1_fee_word(),
             &tx_value,
             transfer_with_gas_fee.sender_balance_prev(),
@@ -791,7 +780,7 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
             tx_value,
             tx_call_data_length,
             is_call_data_empty,
-            tx_call_data_word_length,
+            tx_intrinsic_gas,
             tx_call_data_gas_cost,
             tx_data_gas_cost,
             reversion_info,
@@ -1063,8 +1052,7 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
         )?;
         self.is_call_data_empty
             .assign(region, offset, F::from(tx.call_data_length as u64))?;
-        self.tx_call_data_word_length
-            .assign(region, offset, tx.call_data_length as u128 + 31)?;
+        self.tx_intrinsic_gas.assign(region, offset, tx)?;
         self.tx_call_data_gas_cost.assign(
             region,
             offset,
@@ -1214,6 +1202,7 @@ impl<F: Field> ExecutionGadget<F> for BeginTxGadget<F> {
             region,
             offset,
             tx,
+            block.context.ctxs[&tx.block_number].base_fee,
             tx_l1_fee,
             transfer_assign_result
                 .sender_balance_sub_fee_pair
@@ -1233,7 +1222,10 @@ mod test {
         self, address, bytecode, evm_types::GasCost, word, Address, Bytecode, Hash, Word, U256,
     };
     use ethers_core::{types::Bytes, utils::get_contract_address};
-    use mock::{eth, gwei, MockTransaction, TestContext, MOCK_ACCOUNTS};
+    use mock::{
+        eth, gwei, test_ctx::helpers::sequential_nonces, MockTransaction, TestContext,
+        MOCK_ACCOUNTS,
+    };
 
     fn gas(call_data: &[u8]) -> Word {
         Word::from(
@@ -1693,13 +1685,10 @@ mod test {
             |accs| {
                 accs[0].address(address).nonce(nonce).balance(eth(10));
             },
-            |mut txs, _| {
-                txs[0]
-                    .from(address)
-                    .to(new_address)
-                    .value(eth(2))
-                    .nonce(nonce);
-                txs[1].from(address).nonce(nonce + U256::one());
+            |mut txs, accs| {
+                txs[0].from(address).to(new_address).value(eth(2));
+                txs[1].from(address);
+                sequential_nonces(&mut txs, &accs);
             },
             |block, _| block,
         )
@@ -226,12 +226,13 @@ impl<F: Field> ExecutionGadget<F> for EndTxGadget<F> {
         );
         // rwc_delta = 9 - is_first_tx + !tx_is_l1msg * (coinbase_transfer.rw_delta + 1)
 
-        // The next state of `end_tx` can only be 'begin_tx' or 'end_inner_block'
+        // The next state of `end_tx` can only be 'begin_tx', 'invalid_tx' or 'end_inner_block'
 
         let rw_counter_offset = 9.expr() - is_first_tx.expr()
             + not::expr(tx_is_l1msg.expr()) * (coinbase_transfer.rw_delta() + 1.expr());
         cb.condition(
-            cb.next.execution_state_selector([ExecutionState::BeginTx]),
+            cb.next
+                .execution_state_selector([ExecutionState::BeginTx, ExecutionState::InvalidTx]),
             |cb| {
                 let next_step_rwc = cb.next.state.rw_counter.expr();
                 // lookup use next step initial rwc, thus lead to same record on rw table
@@ -472,7 +473,14 @@ mod test {
     use bus_mapping::circuit_input_builder::CircuitsParams;
     use eth_types::{self, bytecode};
 
-    use mock::{eth, test_ctx::helpers::account_0_code_account_1_no_code, TestContext};
+    use mock::{
+        eth,
+        test_ctx::helpers::{
+            account_0_code_account_1_no_code, deployment_input, sequential_nonces,
+            wallets_and_contracts,
+        },
+        TestContext, MOCK_WALLETS,
+    };
 
     fn test_ok<const NACC: usize, const NTX: usize>(ctx: TestContext<NACC, NTX>) {
         CircuitTestBuilder::new_from_test_ctx(ctx)
@@ -523,6 +531,30 @@ mod test {
             )
             .unwrap(),
         );
+
+        // Interleaved senders, with a deployment
+        test_ok(
+            TestContext::<3, 3>::new(
+                None,
+                wallets_and_contracts(2, vec![bytecode! { STOP }]),
+                |mut txs, accs| {
+                    txs[0]
+                        .from(MOCK_WALLETS[0].clone())
+                        .to(accs[2].address)
+                        .value(eth(1));
+                    txs[1]
+                        .from(MOCK_WALLETS[1].clone())
+                        .input(deployment_input(&bytecode! { STOP }));
+                    txs[2]
+                        .from(MOCK_WALLETS[0].clone())
+                        .to(accs[2].address)
+                        .value(eth(1));
+                    sequential_nonces(&mut txs, &accs);
+                },
+                |block, _tx| block.number(0xcafeu64),
+            )
+            .unwrap(),
+        );
     }
 
     #[test]
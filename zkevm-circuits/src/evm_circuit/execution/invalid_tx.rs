@@ -0,0 +1,543 @@
+//! InvalidTxGadget proves that a transaction included in the block fails one of
+//! the pre-execution checks, so it's skipped without touching the state.
+//! Reference the geth code as:
+//! <https://github.com/ethereum/go-ethereum/blob/master/core/state_transition.go#L234>
+
+use crate::{
+    evm_circuit::{
+        execution::ExecutionGadget,
+        param::N_BYTES_GAS,
+        step::ExecutionState,
+        util::{
+            common_gadget::{TxIntrinsicGasGadget, TxL1FeeGadget},
+            constraint_builder::{
+                ConstrainBuilderCommon, EVMConstraintBuilder, StepStateTransition,
+                Transition::{Delta, To},
+            },
+            math_gadget::{
+                AddWordsGadget, IsEqualGadget, LtGadget, LtWordGadget, MulWordByU64Gadget,
+            },
+            CachedRegion, Cell, StepRws, Word,
+        },
+        witness::{Block, Call, ExecStep, Transaction},
+    },
+    table::{
+        AccountFieldTag, CallContextFieldTag, RwTableTag, TxFieldTag as TxContextFieldTag,
+        TxReceiptFieldTag,
+    },
+};
+use eth_types::{
+    evm_types::GasCost,
+    geth_types::{access_list_size, TxType},
+    Field, ToLittleEndian, ToScalar,
+};
+use gadgets::util::{not, select, Expr};
+use halo2_proofs::{circuit::Value, plonk::Error};
+use strum::EnumCount;
+
+#[derive(Clone, Debug)]
+pub(crate) struct InvalidTxGadget<F> {
+    tx_id: Cell<F>,
+    tx_type: Cell<F>,
+    tx_nonce: Cell<F>,
+    tx_gas: Cell<F>,
+    tx_caller_address: Cell<F>,
+    tx_is_create: Cell<F>,
+    tx_call_data_length: Cell<F>,
+    tx_call_data_gas_cost: Cell<F>,
+    tx_data_gas_cost: Cell<F>,
+    tx_access_list_address_len: Cell<F>,
+    tx_access_list_storage_key_len: Cell<F>,
+    tx_gas_price: Word<F>,
+    tx_max_fee_per_gas: Word<F>,
+    tx_value: Word<F>,
+    tx_is_l1msg: IsEqualGadget<F>,
+    tx_is_eip1559: IsEqualGadget<F>,
+    tx_l1_fee: TxL1FeeGadget<F>,
+    tx_intrinsic_gas: TxIntrinsicGasGadget<F>,
+    sender_nonce: Cell<F>,
+    sender_balance: Word<F>,
+    // Error condition
+    // <https://github.com/ethereum/go-ethereum/blob/master/core/state_transition.go#L280>
+    is_nonce_valid: IsEqualGadget<F>,
+    // Error condition
+    // <https://github.com/ethereum/go-ethereum/blob/master/core/state_transition.go#L385>
+    is_insufficient_gas: LtGadget<F, N_BYTES_GAS>,
+    // The fee cap is the gas price of legacy and EIP-2930 txs, and MaxFeePerGas
+    // of EIP-1559 txs.
+    gas_fee_cap: Word<F>,
+    mul_gas_fee_cap_by_gas: MulWordByU64Gadget<F>,
+    balance_check: AddWordsGadget<F, 3, true>,
+    // Error condition
+    // <https://github.com/ethereum/go-ethereum/blob/master/core/state_transition.go#L255>
+    is_insufficient_balance: LtWordGadget<F>,
+    current_cumulative_gas_used: Cell<F>,
+    is_first_tx: IsEqualGadget<F>,
+}
+
+impl<F: Field> ExecutionGadget<F> for InvalidTxGadget<F> {
+    const NAME: &'static str = "InvalidTx";
+
+    const EXECUTION_STATE: ExecutionState = ExecutionState::InvalidTx;
+
+    fn configure(cb: &mut EVMConstraintBuilder<F>) -> Self {
+        // Use rw_counter of the step as call_id, the same as BeginTx.
+        let call_id = cb.curr.state.rw_counter.clone();
+
+        let tx_id = cb.query_cell();
+        cb.call_context_lookup(
+            1.expr(),
+            Some(call_id.expr()),
+            CallContextFieldTag::TxId,
+            tx_id.expr(),
+        ); // rwc_delta += 1
+
+        cb.step_first(|cb| {
+            cb.require_equal("tx_id is initialized to be 1", tx_id.expr(), 1.expr());
+        });
+
+        let [tx_type, tx_nonce, tx_gas, tx_caller_address, tx_is_create, tx_call_data_length, tx_call_data_gas_cost, tx_data_gas_cost, tx_access_list_address_len, tx_access_list_storage_key_len] =
+            [
+                TxContextFieldTag::TxType,
+                TxContextFieldTag::Nonce,
+                TxContextFieldTag::Gas,
+                TxContextFieldTag::CallerAddress,
+                TxContextFieldTag::IsCreate,
+                TxContextFieldTag::CallDataLength,
+                TxContextFieldTag::CallDataGasCost,
+                TxContextFieldTag::TxDataGasCost,
+                TxContextFieldTag::AccessListAddressesLen,
+                TxContextFieldTag::AccessListStorageKeysLen,
+            ]
+            .map(|field_tag| cb.tx_context(tx_id.expr(), field_tag, None));
+        let [tx_gas_price, tx_max_fee_per_gas, tx_value] = [
+            TxContextFieldTag::GasPrice,
+            TxContextFieldTag::MaxFeePerGas,
+            TxContextFieldTag::Value,
+        ]
+        .map(|field_tag| cb.tx_context_as_word(tx_id.expr(), field_tag, None));
+
+        let [tx_is_l1msg, tx_is_eip1559] = [TxType::L1Msg, TxType::Eip1559]
+            .map(|val| IsEqualGadget::construct(cb, tx_type.expr(), (val as u64).expr()));
+        // L1 messages are enforced by the bridge and are never skipped.
+        cb.require_zero("l1 msg tx can not be invalid", tx_is_l1msg.expr());
+
+        let tx_l1_fee = TxL1FeeGadget::construct(cb, tx_id.expr(), tx_data_gas_cost.expr());
+        // rwc_delta += 3
+
+        let sender_nonce = cb.query_cell();
+        let sender_balance = cb.query_word_rlc();
+        cb.account_read(
+            tx_caller_address.expr(),
+            AccountFieldTag::Nonce,
+            sender_nonce.expr(),
+        ); // rwc_delta += 1
+        cb.account_read(
+            tx_caller_address.expr(),
+            AccountFieldTag::Balance,
+            sender_balance.expr(),
+        ); // rwc_delta += 1
+
+        let is_nonce_valid = IsEqualGadget::construct(cb, tx_nonce.expr(), sender_nonce.expr());
+
+        // The access list lengths are zero for txs without an access list.
+        let tx_intrinsic_gas = TxIntrinsicGasGadget::construct(
+            cb,
+            tx_is_create.expr(),
+            tx_call_data_length.expr(),
+            tx_call_data_gas_cost.expr(),
+            tx_access_list_address_len.expr() * GasCost::ACCESS_LIST_PER_ADDRESS.expr()
+                + tx_access_list_storage_key_len.expr()
+                    * GasCost::ACCESS_LIST_PER_STORAGE_KEY.expr(),
+        );
+        let is_insufficient_gas =
+            LtGadget::construct(cb, tx_gas.expr(), tx_intrinsic_gas.gas_cost());
+
+        let gas_fee_cap = cb.query_word_rlc();
+        cb.require_equal(
+            "gas_fee_cap is MaxFeePerGas for dynamic fee txs, otherwise GasPrice",
+            gas_fee_cap.expr(),
+            select::expr(
+                tx_is_eip1559.expr(),
+                tx_max_fee_per_gas.expr(),
+                tx_gas_price.expr(),
+            ),
+        );
+        let mul_gas_fee_cap_by_gas =
+            MulWordByU64Gadget::construct(cb, gas_fee_cap.clone(), tx_gas.expr());
+        let min_balance = cb.query_word_rlc();
+        let balance_check = AddWordsGadget::construct(
+            cb,
+            [
+                mul_gas_fee_cap_by_gas.product().clone(),
+                tx_value.clone(),
+                tx_l1_fee.tx_l1_fee_word().clone(),
+            ],
+            min_balance.clone(),
+        );
+        let is_insufficient_balance = LtWordGadget::construct(cb, &sender_balance, &min_balance);
+
+        cb.require_zero(
+            "tx fails at least one of the nonce, intrinsic gas and balance checks",
+            is_nonce_valid.expr()
+                * not::expr(is_insufficient_gas.expr())
+                * not::expr(is_insufficient_balance.expr()),
+        );
+
+        // The skipped tx has a failed receipt without logs and consumes no gas.
+        cb.tx_receipt_lookup(
+            1.expr(),
+            tx_id.expr(),
+            TxReceiptFieldTag::PostStateOrStatus,
+            0.expr(),
+        );
+        cb.tx_receipt_lookup(
+            1.expr(),
+            tx_id.expr(),
+            TxReceiptFieldTag::LogLength,
+            0.expr(),
+        ); // rwc_delta += 2
+
+        let is_first_tx = IsEqualGadget::construct(cb, tx_id.expr(), 1.expr());
+
+        let current_cumulative_gas_used = cb.query_cell();
+        cb.condition(is_first_tx.expr(), |cb| {
+            cb.require_zero(
+                "current_cumulative_gas_used is zero when tx is first tx",
+                current_cumulative_gas_used.expr(),
+            );
+        });
+
+        cb.condition(not::expr(is_first_tx.expr()), |cb| {
+            cb.tx_receipt_lookup(
+                0.expr(),
+                tx_id.expr() - 1.expr(),
+                TxReceiptFieldTag::CumulativeGasUsed,
+                current_cumulative_gas_used.expr(),
+            );
+        }); // rwc_delta += 1 - is_first_tx
+
+        cb.tx_receipt_lookup(
+            1.expr(),
+            tx_id.expr(),
+            TxReceiptFieldTag::CumulativeGasUsed,
+            current_cumulative_gas_used.expr(),
+        ); // rwc_delta += 1
+
+        // The next state of `invalid_tx` can only be 'begin_tx', 'invalid_tx' or
+        // 'end_inner_block'
+        let rw_counter_offset = 7.expr() + tx_l1_fee.rw_delta() - is_first_tx.expr();
+        cb.condition(
+            cb.next
+                .execution_state_selector([ExecutionState::BeginTx, ExecutionState::InvalidTx]),
+            |cb| {
+                let next_step_rwc = cb.next.state.rw_counter.expr();
+                // lookup use next step initial rwc, thus lead to same record on rw table
+                cb.call_context_lookup_write_with_counter(
+                    next_step_rwc.clone(),
+                    Some(next_step_rwc),
+                    CallContextFieldTag::TxId,
+                    // tx_id has been lookup and range_check above
+                    tx_id.expr() + 1.expr(),
+                );
+
+                cb.require_step_state_transition(StepStateTransition {
+                    rw_counter: Delta(rw_counter_offset.clone()),
+                    ..StepStateTransition::any()
+                });
+            },
+        );
+
+        cb.condition(
+            cb.next
+                .execution_state_selector([ExecutionState::EndInnerBlock]),
+            |cb| {
+                cb.require_step_state_transition(StepStateTransition {
+                    rw_counter: Delta(rw_counter_offset),
+                    // We propagate call_id so that EndBlock can get the last tx_id
+                    // in order to count processed txs.
+                    call_id: To(call_id.expr()),
+                    ..StepStateTransition::any()
+                });
+            },
+        );
+
+        Self {
+            tx_id,
+            tx_type,
+            tx_nonce,
+            tx_gas,
+            tx_caller_address,
+            tx_is_create,
+            tx_call_data_length,
+            tx_call_data_gas_cost,
+            tx_data_gas_cost,
+            tx_access_list_address_len,
+            tx_access_list_storage_key_len,
+            tx_gas_price,
+            tx_max_fee_per_gas,
+            tx_value,
+            tx_is_l1msg,
+            tx_is_eip1559,
+            tx_l1_fee,
+            tx_intrinsic_gas,
+            sender_nonce,
+            sender_balance,
+            is_nonce_valid,
+            is_insufficient_gas,
+            gas_fee_cap,
+            mul_gas_fee_cap_by_gas,
+            balance_check,
+            is_insufficient_balance,
+            current_cumulative_gas_used,
+            is_first_tx,
+        }
+    }
+
+    fn assign_exec_step(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        block: &Block<F>,
+        tx: &Transaction,
+        _call: &Call,
+        step: &ExecStep,
+    ) -> Result<(), Error> {
+        let mut rws = StepRws::new(block, step);
+
+        let rw = rws.next();
+        debug_assert_eq!(rw.tag(), RwTableTag::CallContext);
+        debug_assert_eq!(rw.field_tag(), Some(CallContextFieldTag::TxId as u64));
+        rws.offset_add(3);
+        let sender_nonce = rws.next().account_nonce_pair().1;
+        let sender_balance = rws.next().account_balance_pair().1;
+
+        self.tx_id
+            .assign(region, offset, Value::known(F::from(tx.id as u64)))?;
+        self.tx_type
+            .assign(region, offset, Value::known(F::from(tx.tx_type as u64)))?;
+        self.tx_nonce
+            .assign(region, offset, Value::known(F::from(tx.nonce)))?;
+        self.tx_gas
+            .assign(region, offset, Value::known(F::from(tx.gas)))?;
+        self.tx_caller_address.assign(
+            region,
+            offset,
+            Value::known(
+                tx.caller_address
+                    .to_scalar()
+                    .expect("unexpected Address -> Scalar conversion failure"),
+            ),
+        )?;
+        self.tx_is_create
+            .assign(region, offset, Value::known(F::from(tx.is_create as u64)))?;
+        self.tx_call_data_length.assign(
+            region,
+            offset,
+            Value::known(F::from(tx.call_data_length as u64)),
+        )?;
+        self.tx_call_data_gas_cost.assign(
+            region,
+            offset,
+            Value::known(F::from(tx.call_data_gas_cost)),
+        )?;
+        self.tx_data_gas_cost
+            .assign(region, offset, Value::known(F::from(tx.tx_data_gas_cost)))?;
+        let (address_len, storage_key_len) = access_list_size(&tx.access_list);
+        self.tx_access_list_address_len.assign(
+            region,
+            offset,
+            Value::known(F::from(address_len)),
+        )?;
+        self.tx_access_list_storage_key_len.assign(
+            region,
+            offset,
+            Value::known(F::from(storage_key_len)),
+        )?;
+        self.tx_gas_price
+            .assign(region, offset, Some(tx.gas_price.to_le_bytes()))?;
+        self.tx_max_fee_per_gas
+            .assign(region, offset, Some(tx.max_fee_per_gas.to_le_bytes()))?;
+        self.tx_value
+            .assign(region, offset, Some(tx.value.to_le_bytes()))?;
+        for (gadget, tx_type) in [
+            (&self.tx_is_l1msg, TxType::L1Msg),
+            (&self.tx_is_eip1559, TxType::Eip1559),
+        ] {
+            gadget.assign(
+                region,
+                offset,
+                F::from(tx.tx_type as u64),
+                F::from(tx_type as u64),
+            )?;
+        }
+
+        self.tx_l1_fee.assign(
+            region,
+            offset,
+            tx.l1_fee,
+            tx.l1_fee_committed,
+            tx.tx_data_gas_cost,
+        )?;
+        let tx_l1_fee = tx.l1_fee.tx_l1_fee(tx.tx_data_gas_cost).0;
+
+        let intrinsic_gas_cost = self.tx_intrinsic_gas.assign(region, offset, tx)?;
+
+        self.sender_nonce.assign(
+            region,
+            offset,
+            Value::known(
+                sender_nonce
+                    .to_scalar()
+                    .expect("unexpected U256 -> Scalar conversion failure"),
+            ),
+        )?;
+        self.sender_balance
+            .assign(region, offset, Some(sender_balance.to_le_bytes()))?;
+        self.is_nonce_valid.assign(
+            region,
+            offset,
+            F::from(tx.nonce),
+            sender_nonce
+                .to_scalar()
+                .expect("unexpected U256 -> Scalar conversion failure"),
+        )?;
+
+        self.is_insufficient_gas.assign(
+            region,
+            offset,
+            F::from(tx.gas),
+            F::from(intrinsic_gas_cost),
+        )?;
+
+        let gas_fee_cap = if tx.tx_type.is_eip1559() {
+            tx.max_fee_per_gas
+        } else {
+            tx.gas_price
+        };
+        let gas_fee = gas_fee_cap * tx.gas;
+        let min_balance = gas_fee + tx.value + tx_l1_fee;
+        self.gas_fee_cap
+            .assign(region, offset, Some(gas_fee_cap.to_le_bytes()))?;
+        self.mul_gas_fee_cap_by_gas
+            .assign(region, offset, gas_fee_cap, tx.gas, gas_fee)?;
+        self.balance_check.assign(
+            region,
+            offset,
+            [gas_fee, tx.value, tx_l1_fee.into()],
+            min_balance,
+        )?;
+        self.is_insufficient_balance
+            .assign(region, offset, sender_balance, min_balance)?;
+
+        let current_cumulative_gas_used: u64 = if tx.id == 1 {
+            0
+        } else {
+            // first transaction needs TxReceiptFieldTag::COUNT(3) lookups to tx receipt,
+            // while later transactions need 4 (with one extra cumulative gas read) lookups
+            let rw = &block.rws[(
+                RwTableTag::TxReceipt,
+                (tx.id - 2) * (TxReceiptFieldTag::COUNT + 1) + 2,
+            )];
+            rw.receipt_value()
+        };
+        self.current_cumulative_gas_used.assign(
+            region,
+            offset,
+            Value::known(F::from(current_cumulative_gas_used)),
+        )?;
+        self.is_first_tx
+            .assign(region, offset, F::from(tx.id as u64), F::one())?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::test_util::CircuitTestBuilder;
+    use bus_mapping::circuit_input_builder::CircuitsParams;
+    use eth_types::{bytecode, Word};
+    use mock::{eth, test_ctx::helpers::account_0_code_account_1_no_code, TestContext};
+
+    fn test_ok<const NACC: usize, const NTX: usize>(ctx: TestContext<NACC, NTX>) {
+        CircuitTestBuilder::new_from_test_ctx(ctx)
+            .params(CircuitsParams {
+                max_txs: 5,
+                ..Default::default()
+            })
+            .run();
+    }
+
+    // Only the l1 tracer keeps invalid txs in the block.
+    #[cfg(not(feature = "scroll"))]
+    #[test]
+    fn invalid_tx_nonce() {
+        test_ok(
+            TestContext::<2, 3>::new(
+                None,
+                account_0_code_account_1_no_code(bytecode! { STOP }),
+                |mut txs, accs| {
+                    txs[0]
+                        .to(accs[0].address)
+                        .from(accs[1].address)
+                        .value(eth(1));
+                    txs[1]
+                        .to(accs[0].address)
+                        .from(accs[1].address)
+                        .nonce(Word::from(5))
+                        .value(eth(1));
+                    txs[2]
+                        .to(accs[0].address)
+                        .from(accs[1].address)
+                        .nonce(Word::one())
+                        .value(eth(1));
+                },
+                |block, _tx| block,
+            )
+            .unwrap(),
+        );
+    }
+
+    #[cfg(not(feature = "scroll"))]
+    #[test]
+    fn invalid_tx_intrinsic_gas() {
+        test_ok(
+            TestContext::<2, 2>::new(
+                None,
+                account_0_code_account_1_no_code(bytecode! { STOP }),
+                |mut txs, accs| {
+                    txs[0]
+                        .to(accs[0].address)
+                        .from(accs[1].address)
+                        .gas(Word::from(20_000));
+                    txs[1]
+                        .to(accs[0].address)
+                        .from(accs[1].address)
+                        .nonce(Word::zero());
+                },
+                |block, _tx| block,
+            )
+            .unwrap(),
+        );
+    }
+
+    #[cfg(not(feature = "scroll"))]
+    #[test]
+    fn invalid_tx_insufficient_balance() {
+        test_ok(
+            TestContext::<2, 1>::new(
+                None,
+                account_0_code_account_1_no_code(bytecode! { STOP }),
+                |mut txs, accs| {
+                    txs[0]
+                        .to(accs[0].address)
+                        .from(accs[1].address)
+                        .value(eth(11));
+                },
+                |block, _tx| block,
+            )
+            .unwrap(),
+        );
+    }
+}
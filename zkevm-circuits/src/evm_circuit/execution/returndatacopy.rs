@@ -250,8 +250,12 @@ impl<F: Field> ExecutionGadget<F> for ReturnDataCopyGadget<F> {
 #[cfg(test)]
 mod test {
     use crate::{evm_circuit::test::rand_bytes, test_util::CircuitTestBuilder};
-    use bus_mapping::circuit_input_builder::CircuitsParams;
-    use eth_types::{bytecode, Word};
+    use bus_mapping::{
+        circuit_input_builder::CircuitsParams,
+        evm::{OpcodeId, PrecompileCallArgs},
+        precompile::PrecompileCalls,
+    };
+    use eth_types::{bytecode, word, ToWord, Word};
     use mock::{generate_mock_call_bytecode, test_ctx::TestContext, MockCallBytecodeParams};
 
     fn test_ok_internal(
@@ -354,4 +358,47 @@ mod test {
         test_ok_internal(0, 0x10, 0x10, 0x10, 0x20.into());
         test_ok_internal(0, 0x10, 0x10, 0, 0x2000000.into());
     }
+
+    #[test]
+    fn returndatacopy_gadget_after_precompile() {
+        let test_vector = [
+            PrecompileCallArgs {
+                name: "identity",
+                setup_code: bytecode! {
+                    PUSH30(word!("0x0123456789abcdef0f1e2d3c4b5a6978"))
+                    PUSH1(0x00)
+                    MSTORE
+                },
+                call_data_length: 0x25.into(),
+                ret_offset: 0x40.into(),
+                ret_size: 0x10.into(),
+                address: PrecompileCalls::Identity.address().to_word(),
+                ..Default::default()
+            },
+            PrecompileCallArgs {
+                name: "sha256",
+                setup_code: bytecode! {
+                    PUSH1(0xff)
+                    PUSH1(0x00)
+                    MSTORE
+                },
+                call_data_offset: 0x1f.into(),
+                call_data_length: 0x01.into(),
+                ret_offset: 0x20.into(),
+                ret_size: 0x20.into(),
+                address: PrecompileCalls::Sha256.address().to_word(),
+                ..Default::default()
+            },
+        ];
+
+        for test_call in test_vector.iter() {
+            for call_op in [OpcodeId::CALL, OpcodeId::STATICCALL] {
+                let code = test_call.with_call_op_and_return_data_copy(call_op, 0x83.into());
+                CircuitTestBuilder::new_from_test_ctx(
+                    TestContext::<2, 1>::simple_ctx_with_bytecode(code).unwrap(),
+                )
+                .run();
+            }
+        }
+    }
 }
@@ -171,15 +171,29 @@ mod tests {
     use mock::TestContext;
 
     fn test_ok(offset: usize, size: usize, mem_kind: MemoryKind) {
+        test_ok_with_params(
+            offset,
+            size,
+            mem_kind,
+            CircuitsParams {
+                max_rws: 5500,
+                max_copy_rows: 3000,
+                ..Default::default()
+            },
+        );
+    }
+
+    fn test_ok_with_params(
+        offset: usize,
+        size: usize,
+        mem_kind: MemoryKind,
+        params: CircuitsParams,
+    ) {
         let (code, _) = gen_sha3_code(offset, size, mem_kind);
         CircuitTestBuilder::new_from_test_ctx(
             TestContext::<2, 1>::simple_ctx_with_bytecode(code).unwrap(),
         )
-        .params(CircuitsParams {
-            max_rws: 5500,
-            max_copy_rows: 3000,
-            ..Default::default()
-        })
+        .params(params)
         .run();
     }
 
@@ -204,6 +218,21 @@ mod tests {
         test_ok(0x404, 0x505, MemoryKind::MoreThanSize);
     }
 
+    #[test]
+    fn sha3_gadget_very_large() {
+        // Each hashed byte takes a read and a write row in the copy circuit.
+        test_ok_with_params(
+            0x11,
+            0x2000,
+            MemoryKind::Empty,
+            CircuitsParams {
+                max_rws: 5500,
+                max_copy_rows: 0x4100,
+                ..Default::default()
+            },
+        );
+    }
+
     #[test]
     fn sha3_gadget_overflow_offset_and_zero_size() {
         let bytecode = bytecode! {
@@ -40,6 +40,7 @@ pub enum ExecutionState {
     // Internal state
     BeginTx,
     EndTx,
+    InvalidTx,
     EndInnerBlock,
     EndBlock,
     // Opcode successful cases
@@ -33,11 +33,13 @@ use halo2_proofs::{
 
 mod tx_access_list;
 mod tx_eip1559;
+mod tx_intrinsic_gas;
 mod tx_l1_fee;
 mod tx_l1_msg;
 
 pub(crate) use tx_access_list::TxAccessListGadget;
 pub(crate) use tx_eip1559::TxEip1559Gadget;
+pub(crate) use tx_intrinsic_gas::TxIntrinsicGasGadget;
 pub(crate) use tx_l1_fee::TxL1FeeGadget;
 pub(crate) use tx_l1_msg::TxL1MsgGadget;
 
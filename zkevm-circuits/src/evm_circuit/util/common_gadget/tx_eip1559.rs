@@ -10,11 +10,11 @@ use crate::{
         util::{
             constraint_builder::{ConstrainBuilderCommon, EVMConstraintBuilder},
             math_gadget::{AddWordsGadget, IsEqualGadget, LtWordGadget, MulWordByU64Gadget},
-            sum, Expr, Word,
+            select, sum, Expr, Word,
         },
         witness::Transaction,
     },
-    table::TxFieldTag,
+    table::{BlockContextFieldTag, TxFieldTag},
 };
 use eth_types::{geth_types::TxType, Field, ToLittleEndian, U256};
 use halo2_proofs::plonk::{Error, Expression};
@@ -35,6 +35,14 @@ pub(crate) struct TxEip1559Gadget<F> {
     // Error condition
     // <https://github.com/ethereum/go-ethereum/blob/master/core/state_transition.go#L304>
     gas_fee_cap_lt_gas_tip_cap: LtWordGadget<F>,
+    // Error condition
+    // <https://github.com/ethereum/go-ethereum/blob/master/core/state_transition.go#L309>
+    gas_fee_cap_lt_base_fee: LtWordGadget<F>,
+    base_fee: Word<F>,
+    // The effective gas price is min(gas_fee_cap, base_fee + gas_tip_cap).
+    // <https://github.com/ethereum/go-ethereum/blob/master/core/state_transition.go#L420>
+    add_base_fee_and_gas_tip_cap: AddWordsGadget<F, 2, true>,
+    gas_fee_cap_lt_base_fee_plus_gas_tip_cap: LtWordGadget<F>,
 }
 
 impl<F: Field> TxEip1559Gadget<F> {
@@ -43,6 +51,7 @@ impl<F: Field> TxEip1559Gadget<F> {
         tx_id: Expression<F>,
         tx_type: Expression<F>,
         tx_gas: Expression<F>,
+        tx_gas_price: &Word<F>,
         tx_l1_fee: &Word<F>,
         value: &Word<F>,
         sender_balance: &Word<F>,
@@ -58,6 +67,10 @@ impl<F: Field> TxEip1559Gadget<F> {
             balance_check,
             is_insufficient_balance,
             gas_fee_cap_lt_gas_tip_cap,
+            gas_fee_cap_lt_base_fee,
+            base_fee,
+            add_base_fee_and_gas_tip_cap,
+            gas_fee_cap_lt_base_fee_plus_gas_tip_cap,
         ) = cb.condition(is_eip1559_tx.expr(), |cb| {
             let mul_gas_fee_cap_by_gas =
                 MulWordByU64Gadget::construct(cb, gas_fee_cap.clone(), tx_gas);
@@ -77,19 +90,50 @@ impl<F: Field> TxEip1559Gadget<F> {
             let gas_fee_cap_lt_gas_tip_cap =
                 LtWordGadget::construct(cb, &gas_fee_cap, &gas_tip_cap);
 
+            let base_fee = cb.query_word_rlc();
+            cb.block_lookup(
+                BlockContextFieldTag::BaseFee.expr(),
+                cb.curr.state.block_number.expr(),
+                base_fee.expr(),
+            );
+            let gas_fee_cap_lt_base_fee = LtWordGadget::construct(cb, &gas_fee_cap, &base_fee);
+
             cb.require_zero(
-                "Sender balance must be sufficient, and gas_fee_cap >= gas_tip_cap",
+                "Sender balance must be sufficient, and gas_fee_cap >= max(gas_tip_cap, base_fee)",
                 sum::expr([
                     is_insufficient_balance.expr(),
                     gas_fee_cap_lt_gas_tip_cap.expr(),
+                    gas_fee_cap_lt_base_fee.expr(),
                 ]),
             );
 
+            let base_fee_plus_gas_tip_cap = cb.query_word_rlc();
+            let add_base_fee_and_gas_tip_cap = AddWordsGadget::construct(
+                cb,
+                [base_fee.clone(), gas_tip_cap.clone()],
+                base_fee_plus_gas_tip_cap.clone(),
+            );
+            let gas_fee_cap_lt_base_fee_plus_gas_tip_cap =
+                LtWordGadget::construct(cb, &gas_fee_cap, &base_fee_plus_gas_tip_cap);
+            cb.require_equal(
+                "tx gas price is min(gas_fee_cap, base_fee + gas_tip_cap)",
+                tx_gas_price.expr(),
+                select::expr(
+                    gas_fee_cap_lt_base_fee_plus_gas_tip_cap.expr(),
+                    gas_fee_cap.expr(),
+                    base_fee_plus_gas_tip_cap.expr(),
+                ),
+            );
+
             (
                 mul_gas_fee_cap_by_gas,
                 balance_check,
                 is_insufficient_balance,
                 gas_fee_cap_lt_gas_tip_cap,
+                gas_fee_cap_lt_base_fee,
+                base_fee,
+                add_base_fee_and_gas_tip_cap,
+                gas_fee_cap_lt_base_fee_plus_gas_tip_cap,
             )
         });
 
@@ -101,6 +145,10 @@ impl<F: Field> TxEip1559Gadget<F> {
             balance_check,
             is_insufficient_balance,
             gas_fee_cap_lt_gas_tip_cap,
+            gas_fee_cap_lt_base_fee,
+            base_fee,
+            add_base_fee_and_gas_tip_cap,
+            gas_fee_cap_lt_base_fee_plus_gas_tip_cap,
         }
     }
 
@@ -109,6 +157,7 @@ impl<F: Field> TxEip1559Gadget<F> {
         region: &mut CachedRegion<'_, '_, F>,
         offset: usize,
         tx: &Transaction,
+        base_fee: U256,
         tx_l1_fee: U256,
         sender_balance_prev: U256,
     ) -> Result<(), Error> {
@@ -147,6 +196,23 @@ impl<F: Field> TxEip1559Gadget<F> {
             offset,
             tx.max_fee_per_gas,
             tx.max_priority_fee_per_gas,
+        )?;
+        self.base_fee
+            .assign(region, offset, Some(base_fee.to_le_bytes()))?;
+        self.gas_fee_cap_lt_base_fee
+            .assign(region, offset, tx.max_fee_per_gas, base_fee)?;
+        let base_fee_plus_gas_tip_cap = base_fee + tx.max_priority_fee_per_gas;
+        self.add_base_fee_and_gas_tip_cap.assign(
+            region,
+            offset,
+            [base_fee, tx.max_priority_fee_per_gas],
+            base_fee_plus_gas_tip_cap,
+        )?;
+        self.gas_fee_cap_lt_base_fee_plus_gas_tip_cap.assign(
+            region,
+            offset,
+            tx.max_fee_per_gas,
+            base_fee_plus_gas_tip_cap,
         )
     }
 }
@@ -207,6 +273,17 @@ mod test {
         }
     }
 
+    #[test]
+    fn test_eip1559_tx_with_base_fee() {
+        // The effective gas price is capped by max_fee_per_gas.
+        let ctx = build_ctx_with_base_fee(gwei(80_000), gwei(2), gwei(1), gwei(3) / 2).unwrap();
+        CircuitTestBuilder::new_from_test_ctx(ctx).run();
+
+        // The effective gas price is base_fee + max_priority_fee_per_gas.
+        let ctx = build_ctx_with_base_fee(gwei(80_000), gwei(2), gwei(1) / 2, gwei(1)).unwrap();
+        CircuitTestBuilder::new_from_test_ctx(ctx).run();
+    }
+
     fn build_ctx(
         sender_balance: Word,
         max_fee_per_gas: Word,
@@ -231,4 +308,30 @@ mod test {
             |block, _tx| block.number(0xcafeu64),
         )
     }
+
+    fn build_ctx_with_base_fee(
+        sender_balance: Word,
+        max_fee_per_gas: Word,
+        max_priority_fee_per_gas: Word,
+        base_fee: Word,
+    ) -> Result<TestContext<2, 1>, Error> {
+        TestContext::new(
+            None,
+            |accs| {
+                accs[0].address(MOCK_ACCOUNTS[0]).balance(sender_balance);
+                accs[1].address(MOCK_ACCOUNTS[1]).balance(eth(1));
+            },
+            |mut txs, _accs| {
+                txs[0]
+                    .from(MOCK_ACCOUNTS[0])
+                    .to(MOCK_ACCOUNTS[1])
+                    .gas(30_000.into())
+                    .value(gwei(20_000))
+                    .max_fee_per_gas(max_fee_per_gas)
+                    .max_priority_fee_per_gas(max_priority_fee_per_gas)
+                    .transaction_type(2); // Set tx type to EIP-1559.
+            },
+            |block, _tx| block.number(0xcafeu64).base_fee_per_gas(base_fee),
+        )
+    }
 }
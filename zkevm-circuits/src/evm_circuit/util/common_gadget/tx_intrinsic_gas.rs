@@ -0,0 +1,73 @@
+use super::CachedRegion;
+use crate::{
+    evm_circuit::{
+        param::N_BYTES_U64,
+        util::{constraint_builder::EVMConstraintBuilder, math_gadget::ConstantDivisionGadget},
+    },
+    util::Expr,
+    witness::Transaction,
+};
+use eth_types::{
+    evm_types::{GasCost, CREATE_GAS_PER_CODE_WORD},
+    Field,
+};
+use gadgets::util::select;
+use halo2_proofs::plonk::{Error, Expression};
+
+/// Intrinsic gas of a transaction, which is charged before its execution begins. It's shared by
+/// BeginTx and InvalidTx, and matches
+/// [`tx_intrinsic_gas`](eth_types::evm_types::gas_utils::tx_intrinsic_gas) of the witness.
+#[derive(Clone, Debug)]
+pub(crate) struct TxIntrinsicGasGadget<F> {
+    tx_call_data_word_length: ConstantDivisionGadget<F, N_BYTES_U64>,
+    gas_cost: Expression<F>,
+}
+
+impl<F: Field> TxIntrinsicGasGadget<F> {
+    pub(crate) fn construct(
+        cb: &mut EVMConstraintBuilder<F>,
+        tx_is_create: Expression<F>,
+        tx_call_data_length: Expression<F>,
+        tx_call_data_gas_cost: Expression<F>,
+        tx_access_list_gas_cost: Expression<F>,
+    ) -> Self {
+        let tx_call_data_word_length =
+            ConstantDivisionGadget::construct(cb, tx_call_data_length + 31.expr(), 32);
+
+        // The init code costs gas only with EIP-3860 of Shanghai.
+        let init_code_gas_cost = select::expr(
+            tx_is_create.expr(),
+            tx_call_data_word_length.quotient().expr() * CREATE_GAS_PER_CODE_WORD.expr(),
+            0.expr(),
+        );
+
+        let gas_cost = select::expr(
+            tx_is_create,
+            GasCost::CREATION_TX.expr(),
+            GasCost::TX.expr(),
+        ) + tx_call_data_gas_cost
+            + tx_access_list_gas_cost
+            + init_code_gas_cost;
+
+        Self {
+            tx_call_data_word_length,
+            gas_cost,
+        }
+    }
+
+    pub(crate) fn assign(
+        &self,
+        region: &mut CachedRegion<'_, '_, F>,
+        offset: usize,
+        tx: &Transaction,
+    ) -> Result<u64, Error> {
+        self.tx_call_data_word_length
+            .assign(region, offset, tx.call_data_length as u128 + 31)?;
+
+        Ok(tx.intrinsic_gas_cost())
+    }
+
+    pub(crate) fn gas_cost(&self) -> Expression<F> {
+        self.gas_cost.clone()
+    }
+}
@@ -340,6 +340,24 @@ mod tests {
             ],
             true,
         );
+        // max * max + 0 == (max - 1) * 2**256 + 1
+        try_test!(
+            MulAddWords512GadgetContainer<Fr>,
+            [
+                Word::MAX,
+                Word::MAX,
+                Word::MAX - 1,
+                Word::from(1),
+                Word::from(0),
+            ],
+            true,
+        );
+        // max * max + max == max * 2**256 + 0, the largest value in 512 bits
+        try_test!(
+            MulAddWords512GadgetContainer<Fr>,
+            [Word::MAX, Word::MAX, Word::MAX, Word::from(0), Word::MAX,],
+            true,
+        );
     }
 
     #[test]
@@ -1,23 +1,24 @@
 use super::{cell_manager::*, param::*, util::*};
 use crate::{evm_circuit::util::rlc, util::Challenges};
+use bus_mapping::circuit_input_builder::keccak_rows_per_round;
 use eth_types::Field;
 use halo2_proofs::{
     circuit::Value,
     plonk::{Error, Expression},
 };
+use keccak256::{common::State, plain::KeccakF};
 use log::{debug, trace};
-use rayon::{iter::IntoParallelRefIterator, prelude::ParallelIterator};
-use std::{env::var, vec};
+use rayon::iter::{
+    IndexedParallelIterator, IntoParallelIterator, IntoParallelRefIterator, ParallelIterator,
+};
+use std::vec;
 
 const MAX_DEGREE: usize = 9;
 
 /// Obtain the rows required for 1 iteration of f-box's inner round
 /// function (consisting of 5 phases) within Keccak circuit
 pub fn get_num_rows_per_round() -> usize {
-    let r = var("KECCAK_ROWS")
-        .unwrap_or_else(|_| format!("{DEFAULT_KECCAK_ROWS}"))
-        .parse()
-        .expect("Cannot parse KECCAK_ROWS env var as usize");
+    let r = keccak_rows_per_round();
     assert!(
         r > NUM_BYTES_PER_WORD,
         "env variable KECCAK_ROWS must be greater than (NUM_BYTES_PER_WORD + 1)."
@@ -545,10 +546,8 @@ pub(crate) fn keccak<F: Field>(
     challenges: Challenges<Value<F>>,
 ) {
     let mut bits = into_bits(bytes);
-    let mut s = [[F::zero(); 5]; 5];
     let absorb_positions = get_absorb_positions();
     let num_bytes_in_last_block = bytes.len() % RATE;
-    let two = F::from(2u64);
 
     // Padding
     bits.push(1);
@@ -557,334 +556,378 @@ pub(crate) fn keccak<F: Field>(
     }
     bits.push(1);
 
-    let mut length = 0usize;
-    let mut data_rlc = Value::known(F::zero());
-    let chunks = bits.chunks(RATE_IN_BITS);
+    // The state and data rlc at the start of a block only depend on the previous blocks, so they
+    // are computed with the native permutation first and the rows of the blocks in parallel.
+    let chunks = bits.chunks(RATE_IN_BITS).collect::<Vec<_>>();
     let num_chunks = chunks.len();
-    for (idx, chunk) in chunks.enumerate() {
-        let is_final_block = idx == num_chunks - 1;
+    let keccak_f = KeccakF::default();
+    let mut state: State = [[0; 5]; 5];
+    let mut data_rlc = Value::known(F::zero());
+    let block_inputs = chunks
+        .iter()
+        .zip(bytes.chunks(RATE).map(Some).chain(std::iter::repeat(None)))
+        .map(|(chunk, block_bytes)| {
+            let input = (state.map(|lanes| lanes.map(pack_u64::<F>)), data_rlc);
+            for (idx, &(i, j)) in absorb_positions.iter().enumerate() {
+                state[i][j] ^= chunk[idx * 64..(idx + 1) * 64]
+                    .iter()
+                    .rev()
+                    .fold(0u64, |lane, &bit| lane << 1 | bit as u64);
+            }
+            keccak_f.permutations(&mut state);
+            for &byte in block_bytes.unwrap_or_default() {
+                data_rlc = rlc::accumulate(data_rlc, byte, challenges.keccak_input());
+            }
+            input
+        })
+        .collect::<Vec<_>>();
+
+    // Collecting an indexed parallel iterator keeps the blocks in order.
+    let block_rows = block_inputs
+        .into_par_iter()
+        .zip(chunks.par_iter())
+        .enumerate()
+        .map(|(idx, ((s, data_rlc), chunk))| {
+            keccak_block(
+                chunk,
+                s,
+                idx * RATE,
+                data_rlc,
+                idx == num_chunks - 1,
+                num_bytes_in_last_block,
+                challenges,
+            )
+        })
+        .collect::<Vec<_>>();
+    rows.extend(block_rows.into_iter().flatten());
 
-        let mut absorb_rows = Vec::new();
-        // Absorb
-        for (idx, &(i, j)) in absorb_positions.iter().enumerate() {
-            let absorb = pack(&chunk[idx * 64..(idx + 1) * 64]);
-            let from = s[i][j];
-            s[i][j] = field_xor(s[i][j], absorb);
-            absorb_rows.push(AbsorbData {
-                from,
-                absorb,
-                result: s[i][j],
-            });
-        }
+    if log::log_enabled!(log::Level::Debug) {
+        let hash_bytes = state
+            .into_iter()
+            .take(4)
+            .flat_map(|lanes| lanes[0].to_le_bytes())
+            .collect::<Vec<_>>();
+        trace!("hash: {:x?}", hash_bytes);
+        trace!("data rlc: {:x?}", data_rlc);
+    }
+}
 
-        let mut hash_words: Vec<F> = Vec::new();
+/// Generate the rows of one block from the state, length and data rlc before the block.
+fn keccak_block<F: Field>(
+    chunk: &[u8],
+    mut s: [[F; 5]; 5],
+    mut length: usize,
+    mut data_rlc: Value<F>,
+    is_final_block: bool,
+    num_bytes_in_last_block: usize,
+    challenges: Challenges<Value<F>>,
+) -> Vec<KeccakRow<F>> {
+    let absorb_positions = get_absorb_positions();
+    let two = F::from(2u64);
+    let mut rows = Vec::with_capacity(get_num_rows_per_update());
+
+    let mut absorb_rows = Vec::new();
+    // Absorb
+    for (idx, &(i, j)) in absorb_positions.iter().enumerate() {
+        let absorb = pack(&chunk[idx * 64..(idx + 1) * 64]);
+        let from = s[i][j];
+        s[i][j] = field_xor(s[i][j], absorb);
+        absorb_rows.push(AbsorbData {
+            from,
+            absorb,
+            result: s[i][j],
+        });
+    }
+
+    let mut hash_words: Vec<F> = Vec::new();
 
-        let mut cell_managers = Vec::new();
-        let mut regions = Vec::new();
+    let mut cell_managers = Vec::new();
+    let mut regions = Vec::new();
 
-        let mut hash_rlc = Value::known(F::zero());
-        let mut round_lengths = Vec::new();
-        let mut round_data_rlcs = Vec::new();
-        for round in 0..NUM_ROUNDS + 1 {
-            let mut cell_manager = CellManager::new(get_num_rows_per_round());
-            let mut region = KeccakRegion::new();
+    let mut hash_rlc = Value::known(F::zero());
+    let mut round_lengths = Vec::new();
+    let mut round_data_rlcs = Vec::new();
+    for round in 0..NUM_ROUNDS + 1 {
+        let mut cell_manager = CellManager::new(get_num_rows_per_round());
+        let mut region = KeccakRegion::new();
+
+        let mut absorb_row = AbsorbData::default();
+        if round < NUM_WORDS_TO_ABSORB {
+            absorb_row = absorb_rows[round].clone();
+        }
 
-            let mut absorb_row = AbsorbData::default();
-            if round < NUM_WORDS_TO_ABSORB {
-                absorb_row = absorb_rows[round].clone();
+        // State data
+        for s in &s {
+            for s in s {
+                let cell = cell_manager.query_cell_value();
+                cell.assign(&mut region, 0, *s);
             }
+        }
 
-            // State data
-            for s in &s {
-                for s in s {
-                    let cell = cell_manager.query_cell_value();
-                    cell.assign(&mut region, 0, *s);
-                }
+        // Absorb data
+        let absorb_from = cell_manager.query_cell_value();
+        let absorb_data = cell_manager.query_cell_value();
+        let absorb_result = cell_manager.query_cell_value();
+        absorb_from.assign(&mut region, 0, absorb_row.from);
+        absorb_data.assign(&mut region, 0, absorb_row.absorb);
+        absorb_result.assign(&mut region, 0, absorb_row.result);
+
+        // Column padding
+        if get_num_rows_per_round() > 28 {
+            for _ in 28..get_num_rows_per_round() {
+                let padding_cell = cell_manager.query_cell_value();
+                padding_cell.assign(&mut region, 0, F::zero());
+            }
+        }
+
+        // Absorb
+        cell_manager.start_region();
+        let part_size = get_num_bits_per_absorb_lookup();
+        let input = absorb_row.from + absorb_row.absorb;
+        let absorb_fat = split::value(&mut cell_manager, &mut region, input, 0, part_size);
+        cell_manager.start_region();
+        let _absorb_result = transform::value(
+            &mut cell_manager,
+            &mut region,
+            absorb_fat.clone(),
+            true,
+            |v| v & 1,
+            true,
+        );
+
+        // Padding
+        cell_manager.start_region();
+        // Unpack a single word into bytes (for the absorption)
+        // Potential optimization: could do multiple bytes per lookup
+        let packed = split::value(&mut cell_manager, &mut region, absorb_row.absorb, 0, 8);
+        cell_manager.start_region();
+        let input_bytes =
+            transform::value(&mut cell_manager, &mut region, packed, false, |v| *v, true);
+        cell_manager.start_region();
+        let mut is_paddings = Vec::new();
+        let mut data_rlcs = vec![Value::known(F::zero()); get_num_rows_per_round()];
+        for _ in input_bytes.iter() {
+            is_paddings.push(cell_manager.query_cell_value());
+        }
+        if round < NUM_WORDS_TO_ABSORB {
+            let mut paddings = Vec::new();
+            for (padding_idx, is_padding) in is_paddings.iter_mut().enumerate() {
+                let byte_idx = round * 8 + padding_idx;
+                let padding = if is_final_block && byte_idx >= num_bytes_in_last_block {
+                    true
+                } else {
+                    length += 1;
+                    false
+                };
+                paddings.push(padding);
+                is_padding.assign(&mut region, 0, if padding { F::one() } else { F::zero() });
             }
 
-            // Absorb data
-            let absorb_from = cell_manager.query_cell_value();
-            let absorb_data = cell_manager.query_cell_value();
-            let absorb_result = cell_manager.query_cell_value();
-            absorb_from.assign(&mut region, 0, absorb_row.from);
-            absorb_data.assign(&mut region, 0, absorb_row.absorb);
-            absorb_result.assign(&mut region, 0, absorb_row.result);
-
-            // Column padding
-            if get_num_rows_per_round() > 28 {
-                for _ in 28..get_num_rows_per_round() {
-                    let padding_cell = cell_manager.query_cell_value();
-                    padding_cell.assign(&mut region, 0, F::zero());
+            data_rlcs[NUM_BYTES_PER_WORD] = data_rlc; // Start at 0 or forward the previous value.
+            for (idx, (byte, padding)) in input_bytes.iter().zip(paddings.iter()).enumerate() {
+                if !*padding {
+                    let byte_value = Value::known(byte.value);
+                    data_rlc = data_rlc * challenges.keccak_input() + byte_value;
                 }
+                data_rlcs[NUM_BYTES_PER_WORD - (idx + 1)] = data_rlc; // data_rlc_after_this_byte
             }
+        } else {
+            // In rounds without inputs, forward the previous value.
+            data_rlcs[0] = data_rlc;
+        }
+        // Other positions of data_rlcs are not constrained and we leave them at 0.
 
-            // Absorb
-            cell_manager.start_region();
-            let part_size = get_num_bits_per_absorb_lookup();
-            let input = absorb_row.from + absorb_row.absorb;
-            let absorb_fat = split::value(&mut cell_manager, &mut region, input, 0, part_size);
-            cell_manager.start_region();
-            let _absorb_result = transform::value(
-                &mut cell_manager,
-                &mut region,
-                absorb_fat.clone(),
-                true,
-                |v| v & 1,
-                true,
-            );
+        cell_manager.start_region();
 
-            // Padding
-            cell_manager.start_region();
-            // Unpack a single word into bytes (for the absorption)
-            // Potential optimization: could do multiple bytes per lookup
-            let packed = split::value(&mut cell_manager, &mut region, absorb_row.absorb, 0, 8);
-            cell_manager.start_region();
-            let input_bytes =
-                transform::value(&mut cell_manager, &mut region, packed, false, |v| *v, true);
+        if round != NUM_ROUNDS {
+            // Theta
+            let part_size = get_num_bits_per_theta_c_lookup();
+            let mut bcf = Vec::new();
+            for s in &s {
+                let c = s[0] + s[1] + s[2] + s[3] + s[4];
+                let bc_fat = split::value(&mut cell_manager, &mut region, c, 1, part_size);
+                bcf.push(bc_fat);
+            }
             cell_manager.start_region();
-            let mut is_paddings = Vec::new();
-            let mut data_rlcs = vec![Value::known(F::zero()); get_num_rows_per_round()];
-            for _ in input_bytes.iter() {
-                is_paddings.push(cell_manager.query_cell_value());
+            let mut bc = Vec::new();
+            for bc_fat in bcf {
+                let bc_norm = transform::value(
+                    &mut cell_manager,
+                    &mut region,
+                    bc_fat.clone(),
+                    true,
+                    |v| v & 1,
+                    true,
+                );
+                bc.push(bc_norm);
             }
-            if round < NUM_WORDS_TO_ABSORB {
-                let mut paddings = Vec::new();
-                for (padding_idx, is_padding) in is_paddings.iter_mut().enumerate() {
-                    let byte_idx = round * 8 + padding_idx;
-                    let padding = if is_final_block && byte_idx >= num_bytes_in_last_block {
-                        true
-                    } else {
-                        length += 1;
-                        false
-                    };
-                    paddings.push(padding);
-                    is_padding.assign(&mut region, 0, if padding { F::one() } else { F::zero() });
+            cell_manager.start_region();
+            let mut os = [[F::zero(); 5]; 5];
+            for i in 0..5 {
+                let t = decode::value(bc[(i + 4) % 5].clone())
+                    + decode::value(rotate(bc[(i + 1) % 5].clone(), 1, part_size));
+                for j in 0..5 {
+                    os[i][j] = s[i][j] + t;
                 }
+            }
+            s = os;
+            cell_manager.start_region();
 
-                data_rlcs[NUM_BYTES_PER_WORD] = data_rlc; // Start at 0 or forward the previous value.
-                for (idx, (byte, padding)) in input_bytes.iter().zip(paddings.iter()).enumerate() {
-                    if !*padding {
-                        let byte_value = Value::known(byte.value);
-                        data_rlc = data_rlc * challenges.keccak_input() + byte_value;
+            // Rho/Pi
+            let part_size = get_num_bits_per_base_chi_lookup();
+            let target_word_sizes = target_part_sizes(part_size);
+            let num_word_parts = target_word_sizes.len();
+            let mut rho_pi_chi_cells: [[[Vec<Cell<F>>; 5]; 5]; 3] = array_init::array_init(|_| {
+                array_init::array_init(|_| array_init::array_init(|_| Vec::new()))
+            });
+            let mut column_starts = [0usize; 3];
+            for p in 0..3 {
+                column_starts[p] = cell_manager.start_region();
+                let mut row_idx = 0;
+                for j in 0..5 {
+                    for _ in 0..num_word_parts {
+                        for i in 0..5 {
+                            rho_pi_chi_cells[p][i][j]
+                                .push(cell_manager.query_cell_value_at_row(row_idx as i32));
+                        }
+                        row_idx = (row_idx + 1) % get_num_rows_per_round();
                     }
-                    data_rlcs[NUM_BYTES_PER_WORD - (idx + 1)] = data_rlc; // data_rlc_after_this_byte
                 }
-            } else {
-                // In rounds without inputs, forward the previous value.
-                data_rlcs[0] = data_rlc;
             }
-            // Other positions of data_rlcs are not constrained and we leave them at 0.
-
             cell_manager.start_region();
-
-            if round != NUM_ROUNDS {
-                // Theta
-                let part_size = get_num_bits_per_theta_c_lookup();
-                let mut bcf = Vec::new();
-                for s in &s {
-                    let c = s[0] + s[1] + s[2] + s[3] + s[4];
-                    let bc_fat = split::value(&mut cell_manager, &mut region, c, 1, part_size);
-                    bcf.push(bc_fat);
-                }
-                cell_manager.start_region();
-                let mut bc = Vec::new();
-                for bc_fat in bcf {
-                    let bc_norm = transform::value(
+            let mut os_parts: [[Vec<PartValue<F>>; 5]; 5] =
+                array_init::array_init(|_| array_init::array_init(|_| Vec::new()));
+            for (j, os_part) in os_parts.iter_mut().enumerate() {
+                for i in 0..5 {
+                    let s_parts = split_uniform::value(
+                        &rho_pi_chi_cells[0][j][(2 * i + 3 * j) % 5],
                         &mut cell_manager,
                         &mut region,
-                        bc_fat.clone(),
+                        s[i][j],
+                        RHO_MATRIX[i][j],
+                        part_size,
+                    );
+
+                    let s_parts = transform_to::value(
+                        &rho_pi_chi_cells[1][j][(2 * i + 3 * j) % 5],
+                        &mut region,
+                        s_parts.clone(),
                         true,
                         |v| v & 1,
-                        true,
                     );
-                    bc.push(bc_norm);
-                }
-                cell_manager.start_region();
-                let mut os = [[F::zero(); 5]; 5];
-                for i in 0..5 {
-                    let t = decode::value(bc[(i + 4) % 5].clone())
-                        + decode::value(rotate(bc[(i + 1) % 5].clone(), 1, part_size));
-                    for j in 0..5 {
-                        os[i][j] = s[i][j] + t;
-                    }
-                }
-                s = os;
-                cell_manager.start_region();
-
-                // Rho/Pi
-                let part_size = get_num_bits_per_base_chi_lookup();
-                let target_word_sizes = target_part_sizes(part_size);
-                let num_word_parts = target_word_sizes.len();
-                let mut rho_pi_chi_cells: [[[Vec<Cell<F>>; 5]; 5]; 3] =
-                    array_init::array_init(|_| {
-                        array_init::array_init(|_| array_init::array_init(|_| Vec::new()))
-                    });
-                let mut column_starts = [0usize; 3];
-                for p in 0..3 {
-                    column_starts[p] = cell_manager.start_region();
-                    let mut row_idx = 0;
-                    for j in 0..5 {
-                        for _ in 0..num_word_parts {
-                            for i in 0..5 {
-                                rho_pi_chi_cells[p][i][j]
-                                    .push(cell_manager.query_cell_value_at_row(row_idx as i32));
-                            }
-                            row_idx = (row_idx + 1) % get_num_rows_per_round();
-                        }
-                    }
+                    os_part[(2 * i + 3 * j) % 5] = s_parts.clone();
                 }
-                cell_manager.start_region();
-                let mut os_parts: [[Vec<PartValue<F>>; 5]; 5] =
-                    array_init::array_init(|_| array_init::array_init(|_| Vec::new()));
-                for (j, os_part) in os_parts.iter_mut().enumerate() {
-                    for i in 0..5 {
-                        let s_parts = split_uniform::value(
-                            &rho_pi_chi_cells[0][j][(2 * i + 3 * j) % 5],
-                            &mut cell_manager,
-                            &mut region,
-                            s[i][j],
-                            RHO_MATRIX[i][j],
-                            part_size,
-                        );
-
-                        let s_parts = transform_to::value(
-                            &rho_pi_chi_cells[1][j][(2 * i + 3 * j) % 5],
-                            &mut region,
-                            s_parts.clone(),
-                            true,
-                            |v| v & 1,
-                        );
-                        os_part[(2 * i + 3 * j) % 5] = s_parts.clone();
-                    }
-                }
-                cell_manager.start_region();
+            }
+            cell_manager.start_region();
 
-                // Chi
-                let part_size_base = get_num_bits_per_base_chi_lookup();
-                let three_packed = pack::<F>(&vec![3u8; part_size_base]);
-                let mut os = [[F::zero(); 5]; 5];
-                for j in 0..5 {
-                    for i in 0..5 {
-                        let mut s_parts = Vec::new();
-                        for ((part_a, part_b), part_c) in os_parts[i][j]
-                            .iter()
-                            .zip(os_parts[(i + 1) % 5][j].iter())
-                            .zip(os_parts[(i + 2) % 5][j].iter())
-                        {
-                            let value =
-                                three_packed - two * part_a.value + part_b.value - part_c.value;
-                            s_parts.push(PartValue {
-                                num_bits: part_size_base,
-                                rot: j as i32,
-                                value,
-                            });
-                        }
-                        os[i][j] = decode::value(transform_to::value(
-                            &rho_pi_chi_cells[2][i][j],
-                            &mut region,
-                            s_parts.clone(),
-                            true,
-                            |v| CHI_BASE_LOOKUP_TABLE[*v as usize],
-                        ));
+            // Chi
+            let part_size_base = get_num_bits_per_base_chi_lookup();
+            let three_packed = pack::<F>(&vec![3u8; part_size_base]);
+            let mut os = [[F::zero(); 5]; 5];
+            for j in 0..5 {
+                for i in 0..5 {
+                    let mut s_parts = Vec::new();
+                    for ((part_a, part_b), part_c) in os_parts[i][j]
+                        .iter()
+                        .zip(os_parts[(i + 1) % 5][j].iter())
+                        .zip(os_parts[(i + 2) % 5][j].iter())
+                    {
+                        let value = three_packed - two * part_a.value + part_b.value - part_c.value;
+                        s_parts.push(PartValue {
+                            num_bits: part_size_base,
+                            rot: j as i32,
+                            value,
+                        });
                     }
+                    os[i][j] = decode::value(transform_to::value(
+                        &rho_pi_chi_cells[2][i][j],
+                        &mut region,
+                        s_parts.clone(),
+                        true,
+                        |v| CHI_BASE_LOOKUP_TABLE[*v as usize],
+                    ));
                 }
-                s = os;
-                cell_manager.start_region();
-
-                // iota
-                let part_size = get_num_bits_per_absorb_lookup();
-                let input = s[0][0] + pack_u64::<F>(ROUND_CST[round]);
-                let iota_parts =
-                    split::value::<F>(&mut cell_manager, &mut region, input, 0, part_size);
-                cell_manager.start_region();
-                s[0][0] = decode::value(transform::value(
-                    &mut cell_manager,
-                    &mut region,
-                    iota_parts.clone(),
-                    true,
-                    |v| v & 1,
-                    true,
-                ));
             }
+            s = os;
+            cell_manager.start_region();
 
-            // The rlc of the hash
-            let is_final = is_final_block && round == NUM_ROUNDS;
-            hash_rlc = if is_final {
-                let hash_bytes_le = s
-                    .into_iter()
-                    .take(4)
-                    .flat_map(|a| to_bytes::value(&unpack(a[0])))
-                    .rev()
-                    .collect::<Vec<_>>();
-                challenges
-                    .evm_word()
-                    .map(|challenge_value| rlc::value(&hash_bytes_le, challenge_value))
-            } else {
-                Value::known(F::zero())
-            };
+            // iota
+            let part_size = get_num_bits_per_absorb_lookup();
+            let input = s[0][0] + pack_u64::<F>(ROUND_CST[round]);
+            let iota_parts = split::value::<F>(&mut cell_manager, &mut region, input, 0, part_size);
+            cell_manager.start_region();
+            s[0][0] = decode::value(transform::value(
+                &mut cell_manager,
+                &mut region,
+                iota_parts.clone(),
+                true,
+                |v| v & 1,
+                true,
+            ));
+        }
 
-            // The words to squeeze out
-            hash_words = s.into_iter().take(4).map(|a| a[0]).take(4).collect();
-            round_lengths.push(length);
-            round_data_rlcs.push(data_rlcs);
+        // The rlc of the hash
+        let is_final = is_final_block && round == NUM_ROUNDS;
+        hash_rlc = if is_final {
+            let hash_bytes_le = s
+                .into_iter()
+                .take(4)
+                .flat_map(|a| to_bytes::value(&unpack(a[0])))
+                .rev()
+                .collect::<Vec<_>>();
+            challenges
+                .evm_word()
+                .map(|challenge_value| rlc::value(&hash_bytes_le, challenge_value))
+        } else {
+            Value::known(F::zero())
+        };
 
-            cell_managers.push(cell_manager);
-            regions.push(region);
-        }
+        // The words to squeeze out
+        hash_words = s.into_iter().take(4).map(|a| a[0]).take(4).collect();
+        round_lengths.push(length);
+        round_data_rlcs.push(data_rlcs);
 
-        // Now that we know the state at the end of the rounds, set the squeeze data
-        let num_rounds = cell_managers.len();
-        for (idx, word) in hash_words.iter().enumerate() {
-            let cell_manager = &mut cell_managers[num_rounds - 2 - idx];
-            let region = &mut regions[num_rounds - 2 - idx];
+        cell_managers.push(cell_manager);
+        regions.push(region);
+    }
 
-            cell_manager.start_region();
-            let squeeze_packed = cell_manager.query_cell_value();
-            squeeze_packed.assign(region, 0, *word);
+    // Now that we know the state at the end of the rounds, set the squeeze data
+    let num_rounds = cell_managers.len();
+    for (idx, word) in hash_words.iter().enumerate() {
+        let cell_manager = &mut cell_managers[num_rounds - 2 - idx];
+        let region = &mut regions[num_rounds - 2 - idx];
 
-            cell_manager.start_region();
-            let packed = split::value(cell_manager, region, *word, 0, 8);
-            cell_manager.start_region();
-            transform::value(cell_manager, region, packed, false, |v| *v, true);
-        }
+        cell_manager.start_region();
+        let squeeze_packed = cell_manager.query_cell_value();
+        squeeze_packed.assign(region, 0, *word);
 
-        for round in 0..NUM_ROUNDS + 1 {
-            let round_cst = pack_u64(ROUND_CST[round]);
-            for row_idx in 0..get_num_rows_per_round() {
-                rows.push(KeccakRow {
-                    q_enable: row_idx == 0,
-                    q_round: row_idx == 0 && round < NUM_ROUNDS,
-                    q_absorb: row_idx == 0 && round == NUM_ROUNDS,
-                    q_round_last: row_idx == 0 && round == NUM_ROUNDS,
-                    q_padding: row_idx == 0 && round < NUM_WORDS_TO_ABSORB,
-                    q_padding_last: row_idx == 0 && round == NUM_WORDS_TO_ABSORB - 1,
-                    round_cst,
-                    is_final: is_final_block && round == NUM_ROUNDS && row_idx == 0,
-                    length: round_lengths[round],
-                    data_rlc: round_data_rlcs[round][row_idx],
-                    hash_rlc,
-                    cell_values: regions[round].rows[row_idx].clone(),
-                });
-            }
-        }
+        cell_manager.start_region();
+        let packed = split::value(cell_manager, region, *word, 0, 8);
+        cell_manager.start_region();
+        transform::value(cell_manager, region, packed, false, |v| *v, true);
     }
 
-    if log::log_enabled!(log::Level::Debug) {
-        let hash_bytes = s
-            .into_iter()
-            .take(4)
-            .map(|a| {
-                pack_with_base::<F>(&unpack(a[0]), 2)
-                    .to_repr()
-                    .into_iter()
-                    .take(8)
-                    .collect::<Vec<_>>()
-                    .to_vec()
-            })
-            .collect::<Vec<_>>();
-        trace!("hash: {:x?}", &(hash_bytes[0..4].concat()));
-        trace!("data rlc: {:x?}", data_rlc);
+    for round in 0..NUM_ROUNDS + 1 {
+        let round_cst = pack_u64(ROUND_CST[round]);
+        for row_idx in 0..get_num_rows_per_round() {
+            rows.push(KeccakRow {
+                q_enable: row_idx == 0,
+                q_round: row_idx == 0 && round < NUM_ROUNDS,
+                q_absorb: row_idx == 0 && round == NUM_ROUNDS,
+                q_round_last: row_idx == 0 && round == NUM_ROUNDS,
+                q_padding: row_idx == 0 && round < NUM_WORDS_TO_ABSORB,
+                q_padding_last: row_idx == 0 && round == NUM_WORDS_TO_ABSORB - 1,
+                round_cst,
+                is_final: is_final_block && round == NUM_ROUNDS && row_idx == 0,
+                length: round_lengths[round],
+                data_rlc: round_data_rlcs[round][row_idx],
+                hash_rlc,
+                cell_values: regions[round].rows[row_idx].clone(),
+            });
+        }
     }
+
+    rows
 }
 
 /// ...
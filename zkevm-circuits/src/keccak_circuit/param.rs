@@ -1,5 +1,4 @@
 pub(crate) const MAX_DEGREE: usize = 9;
-pub(crate) const DEFAULT_KECCAK_ROWS: usize = 12;
 pub(crate) const ABSORB_LOOKUP_RANGE: usize = 3;
 pub(crate) const THETA_C_LOOKUP_RANGE: usize = 6;
 pub(crate) const RHO_PI_LOOKUP_RANGE: usize = 4;
@@ -1,6 +1,7 @@
 #![allow(unused_imports)]
 use super::*;
 use crate::util::unusable_rows;
+use bus_mapping::circuit_input_builder::DEFAULT_KECCAK_ROWS;
 use eth_types::Field;
 use halo2_proofs::{
     circuit::{Layouter, SimpleFloorPlanner},
@@ -19,6 +19,7 @@ use mpt_zktrie::mpt_circuits::{
     mpt,
     types::Proof,
 };
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
 impl PoseidonLookup for PoseidonTable {
     fn lookup_columns_generic(&self) -> (Column<Fixed>, [Column<Advice>; 6]) {
@@ -87,6 +88,47 @@ impl SubCircuitConfig<Fr> for MptCircuitConfig<Fr> {
     }
 }
 
+impl MptCircuit<Fr> {
+    /// Build the circuit proving `mpt_updates` alone, without the rest of the
+    /// super circuit. `row_limit` of 0 means "dynamic".
+    pub fn new_from_updates(mpt_updates: &witness::MptUpdates, row_limit: usize) -> Self {
+        // The smt traces must be generated in order since every update starts from the root
+        // left by the previous one, but turning them into proofs (which hashes every node on
+        // both paths) is independent per update. `collect` keeps the original order.
+        let traces: Vec<_> = mpt_updates
+            .proof_types
+            .iter()
+            .cloned()
+            .zip_eq(mpt_updates.smt_traces.iter().cloned())
+            .collect();
+        let proofs = traces.into_par_iter().map(Proof::from).collect();
+
+        Self {
+            proofs,
+            row_limit,
+            mpt_updates: mpt_updates.clone(),
+            ..Default::default()
+        }
+    }
+
+    /// Fill the mpt table with the updates of this circuit without proving them, used when
+    /// the mpt circuit itself is not configured.
+    #[cfg(feature = "zktrie")]
+    pub(crate) fn dev_load_table(
+        &self,
+        mpt_table: &MptTable,
+        challenges: &Challenges<Value<Fr>>,
+        layouter: &mut impl Layouter<Fr>,
+    ) -> Result<(), Error> {
+        mpt_table.load(
+            layouter,
+            &self.mpt_updates,
+            self.row_limit,
+            challenges.evm_word(),
+        )
+    }
+}
+
 #[cfg(any(feature = "test", test))]
 impl SubCircuit<Fr> for MptCircuit<Fr> {
     type Config = MptCircuitConfig<Fr>;
@@ -102,20 +144,7 @@ impl SubCircuit<Fr> for MptCircuit<Fr> {
                 );
             }
         }
-        let traces: Vec<_> = block
-            .mpt_updates
-            .proof_types
-            .iter()
-            .cloned()
-            .zip_eq(block.mpt_updates.smt_traces.iter().cloned())
-            .collect();
-
-        Self {
-            proofs: traces.into_iter().map(Proof::from).collect(),
-            row_limit: block.circuits_params.max_mpt_rows,
-            mpt_updates: block.mpt_updates.clone(),
-            ..Default::default()
-        }
+        Self::new_from_updates(&block.mpt_updates, block.circuits_params.max_mpt_rows)
     }
 
     fn min_num_rows_block(block: &witness::Block<Fr>) -> (usize, usize) {
@@ -213,3 +242,43 @@ impl Circuit<Fr> for MptCircuit<Fr> {
         self.synthesize_sub(&mpt_config, &challenges, &mut layouter)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::MptCircuit;
+    use crate::{
+        table::AccountFieldTag,
+        witness::{MptUpdates, Rw},
+    };
+    use eth_types::{Address, Word};
+    use halo2_proofs::{dev::MockProver, halo2curves::bn256::Fr};
+    use mpt_zktrie::state::ZktrieState;
+
+    #[test]
+    fn mpt_circuit_standalone_account_update() {
+        let address = Address::repeat_byte(0x45);
+        let rws = [
+            Rw::Account {
+                rw_counter: 1,
+                is_write: true,
+                account_address: address,
+                field_tag: AccountFieldTag::Balance,
+                value: Word::from(100),
+                value_prev: Word::zero(),
+            },
+            Rw::Account {
+                rw_counter: 2,
+                is_write: true,
+                account_address: address,
+                field_tag: AccountFieldTag::Nonce,
+                value: Word::one(),
+                value_prev: Word::zero(),
+            },
+        ];
+        let updates = MptUpdates::from_rws(&rws, &ZktrieState::default());
+
+        let circuit = MptCircuit::<Fr>::new_from_updates(&updates, 3500);
+        let prover = MockProver::<Fr>::run(12, &circuit, vec![]).unwrap();
+        prover.assert_satisfied_par();
+    }
+}
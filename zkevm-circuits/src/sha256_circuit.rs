@@ -68,10 +68,16 @@ const TABLE16_BLOCK_ROWS: usize = 2114;
 const BLOCK_SIZE_IN_BYTES: usize = BLOCK_SIZE * 4;
 
 impl<F: Field> SHA256Circuit<F> {
+    /// Number of 512-bit blocks after padding an input of `input_len` bytes, i.e. the input
+    /// followed by the 0x80 byte and the 8-byte bit length.
+    fn padded_blocks(input_len: usize) -> usize {
+        (input_len + 9).div_ceil(BLOCK_SIZE_IN_BYTES)
+    }
+
     fn expected_rows(&self) -> usize {
         self.0
             .iter()
-            .map(|evnt| (evnt.input.len()) + 9 / BLOCK_SIZE_IN_BYTES + 1)
+            .map(|evnt| Self::padded_blocks(evnt.input.len()))
             .reduce(|acc, v| acc + v)
             .unwrap_or_default()
             * TABLE16_BLOCK_ROWS
@@ -5,7 +5,7 @@ use halo2_proofs::{
 };
 use rand::rngs::OsRng;
 
-use super::{circuit::*, BLOCK_SIZE};
+use super::{circuit::*, SHA256Circuit, BLOCK_SIZE};
 
 use crate::util::Challenges;
 use halo2_proofs::{
@@ -97,6 +97,13 @@ impl Circuit<Fr> for MyCircuit {
     }
 }
 
+#[test]
+fn padded_blocks() {
+    for (input_len, blocks) in [(0, 1), (55, 1), (56, 2), (64, 2), (119, 2), (120, 3)] {
+        assert_eq!(SHA256Circuit::<Fr>::padded_blocks(input_len), blocks);
+    }
+}
+
 #[test]
 fn vk_stable() {
     let k = 17;
@@ -11,6 +11,8 @@ mod dev;
 #[cfg(any(feature = "test", test))]
 mod test;
 #[cfg(any(feature = "test", test, feature = "test-circuits"))]
+pub use dev::verify_rw_artifact;
+#[cfg(any(feature = "test", test, feature = "test-circuits"))]
 pub use dev::StateCircuit as TestStateCircuit;
 use mpt_zktrie::mpt_circuits::MPTProofType;
 
@@ -41,6 +43,7 @@ use lookups::{Chip as LookupsChip, Config as LookupsConfig, Queries as LookupsQu
 use multiple_precision_integer::{Chip as MpiChip, Config as MpiConfig, Queries as MpiQueries};
 use param::*;
 use random_linear_combination::{Chip as RlcChip, Config as RlcConfig, Queries as RlcQueries};
+use rayon::prelude::{IntoParallelIterator, ParallelIterator};
 use std::marker::PhantomData;
 
 #[cfg(feature = "onephase")]
@@ -116,7 +119,7 @@ impl<F: Field> SubCircuitConfig<F> for StateCircuitConfig<F> {
 
         let rw_counter = MpiChip::configure(meta, selector, rw_table.rw_counter, lookups);
         let tag = BinaryNumberChip::configure(meta, selector, Some(rw_table.tag.into()));
-        let id = MpiChip::configure(meta, selector, rw_table.id, lookups);
+        let id = MpiChip::configure_packed(meta, rw_table.id, lookups);
         let address = MpiChip::configure(meta, selector, rw_table.address, lookups);
 
         let storage_key = RlcChip::configure(
@@ -570,6 +573,21 @@ impl<F: Field> StateCircuitConfig<F> {
     ) -> Result<StateCircuitExports<Assigned<F>>, Error> {
         let rows_len = rows.len();
 
+        // The mpt updates of the last access rows are independent of each other, so their root
+        // transitions are computed in parallel. Only the running state root below is sequential.
+        let root_transitions: Vec<Option<Value<(F, F)>>> = (0..rows_len)
+            .into_par_iter()
+            .map(|offset| {
+                let is_last_access = offset + 1 == rows_len || is_first_access_vec[offset + 1];
+                if !is_last_access {
+                    return None;
+                }
+                updates
+                    .get(&rows[offset])
+                    .map(|update| randomness.map(|randomness| update.root_assignments(randomness)))
+            })
+            .collect();
+
         let mut state_root =
             randomness.map(|randomness| rlc::value(&updates.old_root().to_le_bytes(), randomness));
 
@@ -583,32 +601,32 @@ impl<F: Field> StateCircuitConfig<F> {
                 log::trace!("state circuit assign offset:{} row:{:?}", offset, row);
             }
 
-            if offset > 0 {
-                let prev_row = &rows[offset - 1];
-
-                if *is_first_access {
-                    // If previous row was a last access, we need to update the state root.
-                    state_root = randomness
-                        .zip(state_root)
-                        .map(|(randomness, mut state_root)| {
-                            if let Some(update) = updates.get(prev_row) {
-                                let (new_root, old_root) = update.root_assignments(randomness);
+            // If previous row was a last access, we need to update the state root.
+            if offset > 0 && *is_first_access {
+                if let Some(transition) = root_transitions[offset - 1] {
+                    state_root =
+                        state_root
+                            .zip(transition)
+                            .map(|(state_root, (new_root, old_root))| {
                                 if state_root != old_root {
-                                    log::error!("invalid root randomness {:?}, state_root {:?}, prev_row {:?} update {:?}", 
-                                    randomness, state_root, prev_row, update);
+                                    log::error!(
+                                        "invalid root state_root {:?}, prev_row {:?}",
+                                        state_root,
+                                        rows[offset - 1]
+                                    );
                                     assert_eq!(state_root, old_root);
                                 }
-                                state_root = new_root;
-                            }
-                            if matches!(row.tag(), RwTableTag::CallContext)
-                                && !row.is_write()
-                                && row.value_assignment(randomness) != F::zero()
-                            {
-                                log::error!("invalid call context: {:?}", row);
-                            }
-                            state_root
-                        });
+                                new_root
+                            });
                 }
+                randomness.map(|randomness| {
+                    if matches!(row.tag(), RwTableTag::CallContext)
+                        && !row.is_write()
+                        && row.value_assignment(randomness) != F::zero()
+                    {
+                        log::error!("invalid call context: {:?}", row);
+                    }
+                });
             }
 
             // TODO: Switch from Rw::Start -> Rw::Padding to simplify this logic.
@@ -629,14 +647,16 @@ impl<F: Field> StateCircuitConfig<F> {
             if offset + 1 == rows_len {
                 // The last row is always a last access, so we need to handle the case where the
                 // state root changes because of an mpt lookup on the last row.
-                if let Some(update) = updates.get(row) {
-                    state_root = randomness.zip(state_root).map(|(randomness, state_root)| {
-                        let (new_root, old_root) = update.root_assignments(randomness);
-                        if !state_root.is_zero_vartime() {
-                            assert_eq!(state_root, old_root);
-                        }
-                        new_root
-                    });
+                if let Some(transition) = root_transitions[offset] {
+                    state_root =
+                        state_root
+                            .zip(transition)
+                            .map(|(state_root, (new_root, old_root))| {
+                                if !state_root.is_zero_vartime() {
+                                    assert_eq!(state_root, old_root);
+                                }
+                                new_root
+                            });
                 }
                 let assigned = region.assign_advice(
                     || "last row state_root",
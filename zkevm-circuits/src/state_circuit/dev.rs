@@ -4,12 +4,16 @@ use crate::{
     state_circuit::{StateCircuitConfig, StateCircuitConfigArgs},
     table::{MptTable, RwTable},
     util::{Challenges, SubCircuit, SubCircuitConfig},
+    witness::RwMap,
 };
 use eth_types::Field;
 use halo2_proofs::{
     circuit::{Layouter, SimpleFloorPlanner},
+    dev::{MockProver, VerifyFailure},
+    halo2curves::bn256::Fr,
     plonk::{Advice, Circuit, Column, ConstraintSystem, Error},
 };
+use std::{io, path::Path};
 
 impl<F: Field> Circuit<F> for StateCircuit<F>
 where
@@ -60,6 +64,20 @@ where
     }
 }
 
+/// Rebuild the state circuit from a rw map artifact written by [`RwMap::write_artifact_file`]
+/// and verify it with the MockProver, without tracing the block again.
+pub fn verify_rw_artifact(
+    path: impl AsRef<Path>,
+    degree: u32,
+    n_rows: usize,
+) -> io::Result<Result<(), Vec<VerifyFailure>>> {
+    let circuit = StateCircuit::<Fr>::new(RwMap::read_artifact_file(path)?, n_rows);
+    let instance = circuit.instance();
+    let prover = MockProver::<Fr>::run(degree, &circuit, instance)
+        .map_err(|err| io::Error::new(io::ErrorKind::Other, format!("{err:?}")))?;
+    Ok(prover.verify())
+}
+
 #[derive(Hash, Eq, PartialEq, Clone, Debug)]
 pub enum AdviceColumn {
     IsWrite,
@@ -74,6 +92,7 @@ pub enum AdviceColumn {
     RwCounter,
     RwCounterLimb0,
     RwCounterLimb1,
+    IdLimb1,
     Tag,
     TagBit0,
     TagBit1,
@@ -89,6 +108,7 @@ pub enum AdviceColumn {
     // NonEmptyWitness is the BatchedIsZero chip witness that contains the
     // inverse of the non-zero value if any in [committed_value, value]
     NonEmptyWitness,
+    StateRoot,
 }
 
 impl AdviceColumn {
@@ -106,6 +126,7 @@ impl AdviceColumn {
             Self::RwCounter => config.rw_table.rw_counter,
             Self::RwCounterLimb0 => config.sort_keys.rw_counter.limbs[0],
             Self::RwCounterLimb1 => config.sort_keys.rw_counter.limbs[1],
+            Self::IdLimb1 => config.sort_keys.id.limbs[1],
             Self::Tag => config.rw_table.tag,
             Self::TagBit0 => config.sort_keys.tag.bits[0],
             Self::TagBit1 => config.sort_keys.tag.bits[1],
@@ -119,6 +140,7 @@ impl AdviceColumn {
             Self::InitialValue => config.initial_value,
             Self::IsZero => config.is_non_exist.is_zero,
             Self::NonEmptyWitness => config.is_non_exist.nonempty_witness,
+            Self::StateRoot => config.state_root,
         }
     }
 }
@@ -34,12 +34,12 @@ use strum_macros::EnumIter;
 // - first_different_limb: first index where the limbs differ. We use a BinaryNumberChip here to
 //   reduce the degree of the constraints.
 // - limb_difference: the difference between the limbs at first_different_limb.
-// - limb_difference_inverse: the inverse of limb_difference
 
-//  1. limb_difference fits into 16 bits.
-//  2. limb_difference is not zero because its inverse exists.
-//  3. RLC of the pairwise limb differences before the first_different_limb is zero.
-//  4. limb_difference equals the difference of the limbs at first_different_limb.
+//  1. limb_difference - 1 fits into 16 bits. Since both limbs are 16 bit values, this shows that
+//     limb_difference is in [1, 2^16) with a single lookup into the fixed u16 table, i.e. that it
+//     is not zero without witnessing its inverse.
+//  2. RLC of the pairwise limb differences before the first_different_limb is zero.
+//  3. limb_difference equals the difference of the limbs at first_different_limb.
 
 #[derive(Clone, Copy, Debug, EnumIter)]
 pub enum LimbIndex {
@@ -97,7 +97,6 @@ pub struct Config {
     pub(crate) selector: Column<Fixed>,
     pub first_different_limb: BinaryNumberConfig<LimbIndex, 5>,
     limb_difference: Column<Advice>,
-    limb_difference_inverse: Column<Advice>,
 }
 
 impl Config {
@@ -110,25 +109,18 @@ impl Config {
         let selector = meta.fixed_column();
         let first_different_limb = BinaryNumberChip::configure(meta, selector, None);
         let limb_difference = meta.advice_column();
-        let limb_difference_inverse = meta.advice_column();
 
         let config = Config {
             selector,
             first_different_limb,
             limb_difference,
-            limb_difference_inverse,
         };
 
+        // limb_difference is positive, so the first different limb is strictly increasing.
         lookup.range_check_u16(meta, "limb fits into u16", |meta| {
-            meta.query_advice(limb_difference, Rotation::cur())
-        });
-
-        meta.create_gate("limb_difference is not zero", |meta| {
             let selector = meta.query_fixed(selector, Rotation::cur());
             let limb_difference = meta.query_advice(limb_difference, Rotation::cur());
-            let limb_difference_inverse =
-                meta.query_advice(limb_difference_inverse, Rotation::cur());
-            vec![selector * (1.expr() - limb_difference * limb_difference_inverse)]
+            selector * (limb_difference - 1.expr())
         });
 
         meta.create_gate(
@@ -219,24 +211,16 @@ impl Config {
             offset,
             || Value::known(limb_difference),
         )?;
-        region.assign_advice(
-            || "limb_difference_inverse",
-            self.limb_difference_inverse,
-            offset,
-            || Value::known(limb_difference.invert().unwrap()),
-        )?;
 
         Ok(index)
     }
 
     /// Annotates columns of this gadget embedded within a circuit region.
     pub fn annotate_columns_in_region<F: Field>(&self, region: &mut Region<F>, prefix: &str) {
-        [
-            (self.limb_difference, "LO_limb_difference"),
-            (self.limb_difference_inverse, "LO_limb_difference_inverse"),
-        ]
-        .iter()
-        .for_each(|(col, ann)| region.name_column(|| format!("{prefix}_{ann}"), *col));
+        region.name_column(
+            || format!("{prefix}_LO_limb_difference"),
+            self.limb_difference,
+        );
         // fixed column
         region.name_column(
             || format!("{prefix}_LO_upper_limb_difference"),
@@ -257,10 +241,11 @@ struct Queries<F: Field> {
 impl<F: Field> Queries<F> {
     fn new(meta: &mut VirtualCells<'_, F>, keys: SortKeysConfig, rotation: Rotation) -> Self {
         let tag = keys.tag.value(rotation)(meta);
+        let id_limbs = keys.id.query_limbs(meta, rotation);
         let mut query_advice = |column| meta.query_advice(column, rotation);
         Self {
             tag,
-            id_limbs: keys.id.limbs.map(&mut query_advice),
+            id_limbs,
             address_limbs: keys.address.limbs.map(&mut query_advice),
             field_tag: query_advice(keys.field_tag),
             storage_key_bytes: keys.storage_key.bytes.map(&mut query_advice),
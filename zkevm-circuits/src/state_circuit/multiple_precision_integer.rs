@@ -34,13 +34,29 @@ pub struct Config<T, const N: usize>
 where
     T: ToLimbs<N>,
 {
-    // TODO: we can save a column here by not storing the lsb, and then checking that
-    // value - value_from_limbs(limbs.prepend(0)) fits into a limb.
+    // When `packed` is set, the lsb is not stored: limbs[0] is the value column and the lsb is
+    // value - value_from_limbs(limbs.prepend(0)), which is checked to fit into a limb.
     // Does this apply for RLC's too?
     pub limbs: [Column<Advice>; N],
+    packed: bool,
     _marker: PhantomData<T>,
 }
 
+impl<T: ToLimbs<N>, const N: usize> Config<T, N> {
+    /// Query the limbs at `rotation`, deriving the lsb from the value if it is packed.
+    pub fn query_limbs<F: Field>(
+        &self,
+        meta: &mut VirtualCells<'_, F>,
+        rotation: Rotation,
+    ) -> [Expression<F>; N] {
+        let mut limbs = self.limbs.map(|limb| meta.query_advice(limb, rotation));
+        if self.packed {
+            limbs[0] = limbs[0].clone() - value_from_limbs(&limbs[1..]) * (1u64 << 16).expr();
+        }
+        limbs
+    }
+}
+
 #[derive(Clone)]
 pub struct Queries<F: Field, const N: usize> {
     pub limbs: [Expression<F>; N],
@@ -50,10 +66,8 @@ pub struct Queries<F: Field, const N: usize> {
 impl<F: Field, const N: usize> Queries<F, N> {
     pub fn new<T: ToLimbs<N>>(meta: &mut VirtualCells<'_, F>, c: Config<T, N>) -> Self {
         Self {
-            limbs: c.limbs.map(|limb| meta.query_advice(limb, Rotation::cur())),
-            limbs_prev: c
-                .limbs
-                .map(|limb| meta.query_advice(limb, Rotation::prev())),
+            limbs: c.query_limbs(meta, Rotation::cur()),
+            limbs_prev: c.query_limbs(meta, Rotation::prev()),
         }
     }
 }
@@ -97,7 +111,8 @@ fn assign_to_config<T: ToLimbs<N>, const N: usize, F: Field>(
     offset: usize,
     prefix: &str,
 ) -> Result<(), Error> {
-    for (i, &limb) in value.to_limbs().iter().enumerate() {
+    let skip = config.packed as usize;
+    for (i, &limb) in value.to_limbs().iter().enumerate().skip(skip) {
         region.assign_advice(
             || format!("limb[{i}] in {prefix} mpi"),
             config.limbs[i],
@@ -122,6 +137,8 @@ fn annotate_columns_in_config<T: ToLimbs<N>, const N: usize, F: Field>(
         .limbs
         .iter()
         .zip(annotations.iter())
+        // The value column of a packed config is annotated by its own table.
+        .skip(config.packed as usize)
         .for_each(|(col, ann)| region.name_column(|| format!("{prefix}_{ann}"), *col));
 }
 
@@ -166,8 +183,33 @@ where
 
         Config {
             limbs,
+            packed: false,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Same as [`Self::configure`], but the least significant limb is derived from the value
+    /// instead of being stored, which saves an advice column.
+    pub fn configure_packed(
+        meta: &mut ConstraintSystem<F>,
+        value: Column<Advice>,
+        lookup: lookups::Config,
+    ) -> Config<T, N> {
+        let limbs = std::array::from_fn(|i| if i == 0 { value } else { meta.advice_column() });
+        let config = Config {
+            limbs,
+            packed: true,
             _marker: PhantomData,
+        };
+
+        // The upper limbs and the derived lsb fit into u16, so the value fits into N limbs.
+        for i in 0..N {
+            lookup.range_check_u16(meta, "limb fits into u16", |meta| {
+                config.query_limbs(meta, Rotation::cur())[i].clone()
+            });
         }
+
+        config
     }
 
     pub fn load(&self, _layouter: &mut impl Layouter<F>) -> Result<(), Error> {
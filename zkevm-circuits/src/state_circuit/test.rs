@@ -56,6 +56,73 @@ fn test_state_circuit_ok(
     assert_eq!(verify_result, Ok(()));
 }
 
+#[test]
+fn table_assignments_sorted_deterministically() {
+    // Many stack pointers with several accesses each, so that the sort is split across threads.
+    let stack_ops = (0..4096)
+        .map(|i| {
+            Operation::new(
+                RWCounter::from(i + 1),
+                if i < 1024 { RW::WRITE } else { RW::READ },
+                StackOp::new(1, StackAddress::from(i % 1024), Word::from(i % 1024)),
+            )
+        })
+        .collect_vec();
+    let rw_map = RwMap::from(&OperationContainer {
+        stack: stack_ops,
+        ..Default::default()
+    });
+
+    let rows = rw_map.table_assignments();
+    assert_eq!(rows, rw_map.table_assignments());
+    for (prev, row) in rows.iter().tuple_windows() {
+        assert!(prev.as_key() <= row.as_key());
+        if prev.as_key() == row.as_key() {
+            assert!(prev.rw_counter() < row.rw_counter());
+        }
+    }
+}
+
+#[test]
+fn state_circuit_from_rw_artifact() {
+    let memory_ops = vec![
+        Operation::new(
+            RWCounter::from(1),
+            RW::WRITE,
+            MemoryOp::new_write(1, MemoryAddress::from(0), 32.into(), 0.into()),
+        ),
+        Operation::new(
+            RWCounter::from(2),
+            RW::READ,
+            MemoryOp::new_write(1, MemoryAddress::from(0), 32.into(), 32.into()),
+        ),
+    ];
+    let storage_ops = vec![Operation::new(
+        RWCounter::from(3),
+        RW::WRITE,
+        StorageOp::new(
+            address!("0x0000000000000000000000000000000000000001"),
+            Word::from(0x40),
+            Word::from(32),
+            Word::zero(),
+            1,
+            Word::zero(),
+        ),
+    )];
+    let rw_map = RwMap::from(&OperationContainer {
+        memory: memory_ops,
+        storage: storage_ops,
+        ..Default::default()
+    });
+
+    let path = std::env::temp_dir().join(format!("rw_map_{}.bin", std::process::id()));
+    rw_map.write_artifact_file(&path).unwrap();
+    let result = verify_rw_artifact(&path, 17, N_ROWS);
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(result.unwrap(), Ok(()));
+}
+
 #[test]
 fn degree() {
     let mut meta = ConstraintSystem::<Fr>::default();
@@ -389,6 +456,56 @@ fn address_limb_mismatch() {
     assert_error_matches(result, "mpi value matches claimed limbs");
 }
 
+#[test]
+fn state_root_bound_to_mpt_update() {
+    let rows = vec![Rw::Account {
+        rw_counter: 1,
+        is_write: true,
+        account_address: address!("0x000000000000000000000000000000000cafe002"),
+        field_tag: AccountFieldTag::Nonce,
+        value: U256::one(),
+        value_prev: U256::zero(),
+    }];
+    let overrides = HashMap::from([((AdviceColumn::StateRoot, 0), Fr::from(0xbad))]);
+
+    let result = verify_with_overrides(rows, overrides);
+
+    assert_error_matches(
+        result,
+        "mpt_update exists in mpt circuit for Account last access",
+    );
+}
+
+#[test]
+fn state_root_unchanged_by_memory_rows() {
+    let rows = vec![
+        Rw::Memory {
+            rw_counter: 1,
+            is_write: true,
+            call_id: 1,
+            memory_address: 0,
+            value: U256::from(32),
+            value_prev: U256::zero(),
+        },
+        Rw::Memory {
+            rw_counter: 2,
+            is_write: false,
+            call_id: 1,
+            memory_address: 0,
+            value: U256::from(32),
+            value_prev: U256::from(32),
+        },
+    ];
+    let overrides = HashMap::from([((AdviceColumn::StateRoot, 0), Fr::from(0xbad))]);
+
+    let errors = verify_with_overrides(rows, overrides).expect_err("state root must not change");
+    assert!(errors.iter().all(|error| matches!(
+        error,
+        VerifyFailure::ConstraintNotSatisfied { constraint, .. }
+            if format!("{constraint}").contains("state_root is unchanged for Memory")
+    )));
+}
+
 #[test]
 fn address_limb_out_of_range() {
     let rows = vec![Rw::Account {
@@ -409,6 +526,23 @@ fn address_limb_out_of_range() {
     assert_error_matches(result, "limb fits into u16");
 }
 
+#[test]
+fn id_limb_out_of_range() {
+    let rows = vec![Rw::Stack {
+        rw_counter: 1,
+        is_write: true,
+        call_id: 1,
+        stack_pointer: 1023,
+        value: U256::from(394500u64),
+    }];
+    // The lsb of the id is not stored, so it becomes 1 - 2^16.
+    let overrides = HashMap::from([((AdviceColumn::IdLimb1, 0), Fr::one())]);
+
+    let result = verify_with_overrides(rows, overrides);
+
+    assert_error_matches(result, "limb fits into u16");
+}
+
 #[test]
 fn storage_key_mismatch() {
     let rows = vec![Rw::AccountStorage {
@@ -633,6 +767,21 @@ fn nonlexicographic_order_rw_counter() {
     assert_error_matches(verify(vec![second, first]), "limb fits into u16");
 }
 
+#[test]
+fn duplicate_rw_counter() {
+    let row = Rw::CallContext {
+        rw_counter: 1,
+        is_write: false,
+        call_id: 1,
+        field_tag: CallContextFieldTag::IsSuccess,
+        value: U256::zero(),
+    };
+
+    // All limbs are equal, so the limb difference at first_different_limb is 0, which fails the
+    // range check of limb_difference - 1.
+    assert_error_matches(verify(vec![row, row]), "limb fits into u16");
+}
+
 #[test]
 fn lexicographic_ordering_previous_limb_differences_nonzero() {
     let rows = vec![
@@ -943,6 +1092,72 @@ fn bad_initial_tx_refund_value() {
     );
 }
 
+fn destructed_account_rows(read_nonce: Word, read_storage: Word) -> Vec<Rw> {
+    let address = address!("0x000000000000000000000000000000000000dead");
+    vec![
+        Rw::Account {
+            rw_counter: 2,
+            is_write: true,
+            account_address: address,
+            field_tag: AccountFieldTag::Nonce,
+            value: U256::zero(),
+            value_prev: U256::from(3),
+        },
+        Rw::Account {
+            rw_counter: 4,
+            is_write: false,
+            account_address: address,
+            field_tag: AccountFieldTag::Nonce,
+            value: read_nonce,
+            value_prev: U256::zero(),
+        },
+        Rw::AccountStorage {
+            rw_counter: 1,
+            is_write: true,
+            account_address: address,
+            storage_key: U256::from(5),
+            value: U256::zero(),
+            value_prev: U256::from(9),
+            tx_id: 1,
+            committed_value: U256::from(9),
+        },
+        Rw::AccountStorage {
+            rw_counter: 3,
+            is_write: false,
+            account_address: address,
+            storage_key: U256::from(5),
+            value: read_storage,
+            value_prev: U256::zero(),
+            tx_id: 1,
+            committed_value: U256::from(9),
+        },
+    ]
+}
+
+#[test]
+fn destructed_account_reads_cleared_state() {
+    assert_eq!(
+        verify(destructed_account_rows(Word::zero(), Word::zero())),
+        Ok(())
+    );
+}
+
+#[test]
+fn destructed_account_nonce_cannot_be_read_back() {
+    assert_error_matches(
+        verify(destructed_account_rows(U256::from(3), Word::zero())),
+        "non-first access reads don't change value",
+    );
+}
+
+#[test]
+fn destructed_account_storage_cannot_be_read_back() {
+    assert_error_matches(
+        verify(destructed_account_rows(Word::zero(), U256::from(9))),
+        "non-first access reads don't change value",
+    );
+}
+
 #[test]
 fn bad_initial_tx_log_value() {
     let rows = vec![Rw::TxLog {
@@ -93,10 +93,12 @@ use crate::{
 use crate::mpt_circuit::{MptCircuit, MptCircuitConfig, MptCircuitConfigArgs};
 
 use bus_mapping::{
-    circuit_input_builder::{CircuitInputBuilder, CircuitsParams},
+    circuit_input_builder::{BuilderClient, CircuitInputBuilder, CircuitsParams},
     mock::BlockData,
+    rpc::{GethClient, RpcRetryConfig},
 };
 use eth_types::{geth_types::GethData, Field};
+use ethers_providers::Http;
 use halo2_proofs::{
     circuit::{Layouter, SimpleFloorPlanner, Value},
     halo2curves::bn256::Fr,
@@ -104,6 +106,7 @@ use halo2_proofs::{
 };
 use itertools::Itertools;
 use snark_verifier_sdk::CircuitExt;
+use std::str::FromStr;
 
 /// Configuration of the Super Circuit
 #[derive(Clone)]
@@ -115,6 +118,7 @@ pub struct SuperCircuitConfig<F: Field> {
     poseidon_table: PoseidonTable,
     u8_table: U8Table,
     u16_table: U16Table,
+    exp_table: ExpTable,
     evm_circuit: EvmCircuitConfig<F>,
     state_circuit: StateCircuitConfig<F>,
     tx_circuit: TxCircuitConfig<F>,
@@ -130,11 +134,37 @@ pub struct SuperCircuitConfig<F: Field> {
     keccak_circuit: KeccakCircuitConfig<F>,
     poseidon_circuit: PoseidonCircuitConfig<F>,
     pi_circuit: PiCircuitConfig<F>,
-    exp_circuit: ExpCircuitConfig<F>,
+    /// Exp Circuit, `None` if disabled by [`SubCircuitToggles::exp`]
+    exp_circuit: Option<ExpCircuitConfig<F>>,
     rlp_circuit: RlpCircuitConfig<F>,
-    /// Mpt Circuit
+    /// Mpt Circuit, `None` if disabled by [`SubCircuitToggles::mpt`]
     #[cfg(feature = "zktrie")]
-    mpt_circuit: MptCircuitConfig<F>,
+    mpt_circuit: Option<MptCircuitConfig<F>>,
+}
+
+/// Selects which optional sub-circuits are configured in the super circuit.
+///
+/// A disabled sub-circuit is not configured at all, and the table it would have proven is
+/// filled with unconstrained dev assignments instead, so other sub-circuits can still look it
+/// up. This is only meant for development and targeted testing. The toggles of a
+/// [`SuperCircuit`] are set with [`SuperCircuit::with_sub_circuits`] and reach its
+/// configuration through the circuit params, or directly through
+/// [`SuperCircuit::configure_with_sub_circuits`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SubCircuitToggles {
+    /// Configure the exp circuit, otherwise the exp table is loaded without constraints.
+    pub exp: bool,
+    /// Configure the mpt circuit, otherwise the mpt table is loaded without constraints.
+    pub mpt: bool,
+}
+
+impl Default for SubCircuitToggles {
+    fn default() -> Self {
+        Self {
+            exp: true,
+            mpt: true,
+        }
+    }
 }
 
 /// Circuit configuration arguments
@@ -149,6 +179,8 @@ pub struct SuperCircuitConfigArgs {
     pub mock_randomness: u64,
     /// Challenges
     pub challenges: crate::util::Challenges,
+    /// Optional sub-circuits to configure
+    pub sub_circuits: SubCircuitToggles,
 }
 
 impl SubCircuitConfig<Fr> for SuperCircuitConfig<Fr> {
@@ -163,6 +195,7 @@ impl SubCircuitConfig<Fr> for SuperCircuitConfig<Fr> {
             max_inner_blocks: _,
             mock_randomness: _mock_randomness,
             challenges,
+            sub_circuits,
         }: Self::ConfigArgs,
     ) -> Self {
         let log_circuit_info = |meta: &ConstraintSystem<Fr>, tag: &str| {
@@ -307,14 +340,16 @@ impl SubCircuitConfig<Fr> for SuperCircuitConfig<Fr> {
         log_circuit_info(meta, "copy circuit");
 
         #[cfg(feature = "zktrie")]
-        let mpt_circuit = MptCircuitConfig::new(
-            meta,
-            MptCircuitConfigArgs {
-                poseidon_table,
-                mpt_table,
-                challenges,
-            },
-        );
+        let mpt_circuit = sub_circuits.mpt.then(|| {
+            MptCircuitConfig::new(
+                meta,
+                MptCircuitConfigArgs {
+                    poseidon_table,
+                    mpt_table,
+                    challenges,
+                },
+            )
+        });
         #[cfg(feature = "zktrie")]
         log_circuit_info(meta, "zktrie circuit");
 
@@ -330,13 +365,15 @@ impl SubCircuitConfig<Fr> for SuperCircuitConfig<Fr> {
         );
         log_circuit_info(meta, "state circuit");
 
-        let exp_circuit = ExpCircuitConfig::new(
-            meta,
-            ExpCircuitArgs {
-                exp_table,
-                u16_table,
-            },
-        );
+        let exp_circuit = sub_circuits.exp.then(|| {
+            ExpCircuitConfig::new(
+                meta,
+                ExpCircuitArgs {
+                    exp_table,
+                    u16_table,
+                },
+            )
+        });
         log_circuit_info(meta, "exp circuit");
 
         let evm_circuit = EvmCircuitConfig::new(
@@ -394,6 +431,7 @@ impl SubCircuitConfig<Fr> for SuperCircuitConfig<Fr> {
             poseidon_table,
             u8_table,
             u16_table,
+            exp_table,
             evm_circuit,
             state_circuit,
             copy_circuit,
@@ -426,6 +464,21 @@ pub struct SubcircuitRowUsage {
     pub row_num_total: usize,
 }
 
+/// Row usage of every sub circuit for a block, see [`SuperCircuit::row_usage`]
+#[derive(Clone, Default, Debug)]
+pub struct SuperCircuitRowUsage {
+    /// Per sub circuit usage, in the order of
+    /// [`SuperCircuit::min_num_rows_block_subcircuits`]
+    pub sub_circuits: Vec<SubcircuitRowUsage>,
+}
+
+impl SuperCircuitRowUsage {
+    /// The sub circuit needing the most rows (without padding), which bounds the block
+    pub fn bottleneck(&self) -> Option<&SubcircuitRowUsage> {
+        self.sub_circuits.iter().max_by_key(|r| r.row_num_real)
+    }
+}
+
 /// The Super Circuit contains all the zkEVM circuits
 #[derive(Clone, Debug)]
 pub struct SuperCircuit<
@@ -468,6 +521,7 @@ pub struct SuperCircuit<
     pub mpt_circuit: MptCircuit<F>,
 
     circuit_params: CircuitsParams,
+    sub_circuits: SubCircuitToggles,
 }
 
 impl<
@@ -478,6 +532,34 @@ impl<
         const MOCK_RANDOMNESS: u64,
     > SuperCircuit<F, MAX_TXS, MAX_CALLDATA, MAX_INNER_BLOCKS, MOCK_RANDOMNESS>
 {
+    /// Set the optional sub-circuits to configure.
+    pub fn with_sub_circuits(mut self, sub_circuits: SubCircuitToggles) -> Self {
+        self.sub_circuits = sub_circuits;
+        self
+    }
+
+    /// Configure the super circuit with only the given optional sub-circuits.
+    pub fn configure_with_sub_circuits(
+        meta: &mut ConstraintSystem<Fr>,
+        sub_circuits: SubCircuitToggles,
+    ) -> (SuperCircuitConfig<Fr>, Challenges) {
+        let challenges = Challenges::construct(meta);
+        (
+            SuperCircuitConfig::new(
+                meta,
+                SuperCircuitConfigArgs {
+                    max_txs: MAX_TXS,
+                    max_calldata: MAX_CALLDATA,
+                    max_inner_blocks: MAX_INNER_BLOCKS,
+                    mock_randomness: MOCK_RANDOMNESS,
+                    challenges,
+                    sub_circuits,
+                },
+            ),
+            challenges,
+        )
+    }
+
     /// Return the number of rows required to verify a given block
     pub fn get_num_rows_required(block: &Block<Fr>) -> usize {
         let num_rows_evm_circuit = EvmCircuit::<Fr>::get_num_rows_required(block);
@@ -488,6 +570,13 @@ impl<
         // num_rows_evm_circuit.max(num_rows_tx_circuit)
         num_rows_evm_circuit
     }
+    /// Estimate the rows each sub circuit needs for `block`, without synthesizing
+    pub fn row_usage(block: &Block<Fr>) -> SuperCircuitRowUsage {
+        SuperCircuitRowUsage {
+            sub_circuits: Self::min_num_rows_block_subcircuits(block),
+        }
+    }
+
     /// Return the minimum number of rows required to prove the block
     pub fn min_num_rows_block_subcircuits(block: &Block<Fr>) -> Vec<SubcircuitRowUsage> {
         log::debug!("start min_num_rows_block_subcircuits");
@@ -614,6 +703,7 @@ impl<
             #[cfg(feature = "zktrie")]
             mpt_circuit,
             circuit_params: block.circuits_params,
+            sub_circuits: SubCircuitToggles::default(),
         }
     }
 
@@ -691,9 +781,18 @@ impl<
         log::debug!("assigning copy_circuit");
         self.copy_circuit
             .synthesize_sub(&config.copy_circuit, challenges, layouter)?;
-        log::debug!("assigning exp_circuit");
-        self.exp_circuit
-            .synthesize_sub(&config.exp_circuit, challenges, layouter)?;
+        if let Some(exp_circuit) = &config.exp_circuit {
+            log::debug!("assigning exp_circuit");
+            self.exp_circuit
+                .synthesize_sub(exp_circuit, challenges, layouter)?;
+        } else {
+            log::debug!("exp_circuit disabled, loading exp table without constraints");
+            let block = Block {
+                exp_events: self.exp_circuit.exp_events.clone(),
+                ..Default::default()
+            };
+            config.exp_table.dev_load(layouter, &block)?;
+        }
 
         log::debug!("assigning pi_circuit");
         self.pi_circuit
@@ -712,10 +811,14 @@ impl<
 
         // load both poseidon table and zktrie table
         #[cfg(feature = "zktrie")]
-        {
+        if let Some(mpt_circuit) = &config.mpt_circuit {
             log::debug!("assigning mpt_circuit");
             self.mpt_circuit
-                .synthesize_sub(&config.mpt_circuit, challenges, layouter)?;
+                .synthesize_sub(mpt_circuit, challenges, layouter)?;
+        } else {
+            log::debug!("mpt_circuit disabled, loading mpt table without constraints");
+            self.mpt_circuit
+                .dev_load_table(&config.mpt_table, challenges, layouter)?;
         }
 
         log::debug!("super circuit synthesize_sub done");
@@ -733,31 +836,31 @@ impl<
     type Config = (SuperCircuitConfig<Fr>, Challenges);
     type FloorPlanner = SimpleFloorPlanner;
     #[cfg(feature = "circuit-params")]
-    type Params = ();
+    type Params = SubCircuitToggles;
 
     fn without_witnesses(&self) -> Self {
         let dummy_block = Block::<Fr> {
             circuits_params: self.circuit_params,
             ..Default::default()
         };
-        Self::new_from_block(&dummy_block)
+        Self::new_from_block(&dummy_block).with_sub_circuits(self.sub_circuits)
+    }
+
+    #[cfg(feature = "circuit-params")]
+    fn params(&self) -> Self::Params {
+        self.sub_circuits
+    }
+
+    #[cfg(feature = "circuit-params")]
+    fn configure_with_params(
+        meta: &mut ConstraintSystem<Fr>,
+        sub_circuits: Self::Params,
+    ) -> Self::Config {
+        Self::configure_with_sub_circuits(meta, sub_circuits)
     }
 
     fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
-        let challenges = Challenges::construct(meta);
-        (
-            SuperCircuitConfig::new(
-                meta,
-                SuperCircuitConfigArgs {
-                    max_txs: MAX_TXS,
-                    max_calldata: MAX_CALLDATA,
-                    max_inner_blocks: MAX_INNER_BLOCKS,
-                    mock_randomness: MOCK_RANDOMNESS,
-                    challenges,
-                },
-            ),
-            challenges,
-        )
+        Self::configure_with_sub_circuits(meta, SubCircuitToggles::default())
     }
 
     fn synthesize(
@@ -820,6 +923,27 @@ impl<
         Ok((ret.0, ret.1, ret.2, builder))
     }
 
+    /// Fetch block `block_num` from the node at `url`, run the bus-mapping pipeline on it and
+    /// generate a SuperCircuit instance, returning the same values as [`Self::build`].
+    #[allow(clippy::type_complexity)]
+    pub async fn build_from_rpc(
+        url: &str,
+        block_num: u64,
+        circuits_params: CircuitsParams,
+    ) -> Result<(u32, Self, Vec<Vec<Fr>>, CircuitInputBuilder), bus_mapping::Error> {
+        let provider = Http::from_str(url)
+            .map_err(|_| bus_mapping::Error::InternalError("invalid rpc url"))?;
+        let cli = BuilderClient::new(
+            GethClient::new_with_retry(provider, RpcRetryConfig::default()),
+            circuits_params,
+        )
+        .await?;
+        let (builder, _) = cli.gen_inputs(block_num).await?;
+
+        let ret = Self::build_from_circuit_input_builder(&builder)?;
+        Ok((ret.0, ret.1, ret.2, builder))
+    }
+
     /// From CircuitInputBuilder, generate a SuperCircuit instance with all of
     /// the sub-circuits filled with their corresponding witnesses.
     ///
@@ -833,6 +957,14 @@ impl<
         assert_eq!(block.circuits_params.max_calldata, MAX_CALLDATA);
         Self::build_from_witness_block(block)
     }
+
+    /// Return the minimum degree `k` of a super circuit able to prove `block`, i.e. enough
+    /// rows for the largest sub-circuit plus the unusable rows at the end.
+    pub fn min_k_for(block: &Block<Fr>) -> u32 {
+        let (_, rows_needed) = Self::min_num_rows_block(block);
+        log2_ceil(Self::unusable_rows() + rows_needed)
+    }
+
     /// ..
     pub fn build_from_witness_block(
         block: Block<Fr>,
@@ -842,8 +974,7 @@ impl<
             block.circuits_params
         );
 
-        let (_, rows_needed) = Self::min_num_rows_block(&block);
-        let k = log2_ceil(Self::unusable_rows() + rows_needed);
+        let k = Self::min_k_for(&block);
         log::debug!("super circuit needs k = {}", k);
 
         let circuit =
@@ -51,9 +51,54 @@ fn super_circuit_degree() {
     SuperCircuit::<Fr, 1, 32, 64, 0x100>::configure(&mut cs);
     log::info!("super circuit degree: {}", cs.degree());
     log::info!("super circuit minimum_rows: {}", cs.minimum_rows());
+    log::debug!("super circuit stats: {}", circuit_stats(&cs).to_json());
     assert!(cs.degree() <= 9);
 }
 
+/// Super circuit configured without the exp circuit, so its exp table is loaded without
+/// constraints.
+#[cfg(feature = "scroll")]
+#[derive(Clone, Debug)]
+struct SuperCircuitWithoutExp<
+    const MAX_TXS: usize,
+    const MAX_CALLDATA: usize,
+    const MAX_INNER_BLOCKS: usize,
+    const MOCK_RANDOMNESS: u64,
+>(SuperCircuit<Fr, MAX_TXS, MAX_CALLDATA, MAX_INNER_BLOCKS, MOCK_RANDOMNESS>);
+
+#[cfg(feature = "scroll")]
+impl<
+        const MAX_TXS: usize,
+        const MAX_CALLDATA: usize,
+        const MAX_INNER_BLOCKS: usize,
+        const MOCK_RANDOMNESS: u64,
+    > Circuit<Fr>
+    for SuperCircuitWithoutExp<MAX_TXS, MAX_CALLDATA, MAX_INNER_BLOCKS, MOCK_RANDOMNESS>
+{
+    type Config = (SuperCircuitConfig<Fr>, Challenges);
+    type FloorPlanner = SimpleFloorPlanner;
+    #[cfg(feature = "circuit-params")]
+    type Params = ();
+
+    fn without_witnesses(&self) -> Self {
+        Self(self.0.without_witnesses())
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fr>) -> Self::Config {
+        SuperCircuit::<Fr, MAX_TXS, MAX_CALLDATA, MAX_INNER_BLOCKS, MOCK_RANDOMNESS>::configure_with_sub_circuits(
+            meta,
+            SubCircuitToggles {
+                exp: false,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn synthesize(&self, config: Self::Config, layouter: impl Layouter<Fr>) -> Result<(), Error> {
+        self.0.synthesize(config, layouter)
+    }
+}
+
 #[cfg(feature = "scroll")]
 fn test_super_circuit<
     const MAX_TXS: usize,
@@ -63,6 +108,25 @@ fn test_super_circuit<
 >(
     l2_trace: BlockTrace,
     circuits_params: CircuitsParams,
+) {
+    test_super_circuit_with::<MAX_TXS, MAX_CALLDATA, MAX_INNER_BLOCKS, MOCK_RANDOMNESS, _>(
+        l2_trace,
+        circuits_params,
+        |circuit| circuit,
+    );
+}
+
+#[cfg(feature = "scroll")]
+fn test_super_circuit_with<
+    const MAX_TXS: usize,
+    const MAX_CALLDATA: usize,
+    const MAX_INNER_BLOCKS: usize,
+    const MOCK_RANDOMNESS: u64,
+    C: Circuit<Fr>,
+>(
+    l2_trace: BlockTrace,
+    circuits_params: CircuitsParams,
+    wrap: impl FnOnce(SuperCircuit<Fr, MAX_TXS, MAX_CALLDATA, MAX_INNER_BLOCKS, MOCK_RANDOMNESS>) -> C,
 ) {
     set_var("COINBASE", "0x0000000000000000000000000000000000000000");
     set_var("CHAIN_ID", MOCK_CHAIN_ID.to_string());
@@ -99,7 +163,7 @@ fn test_super_circuit<
         MOCK_RANDOMNESS,
     >::build_from_witness_block(block)
     .unwrap();
-    let prover = MockProver::run(k, &circuit, instance).unwrap();
+    let prover = MockProver::run(k, &wrap(circuit), instance).unwrap();
 
     let res = prover.verify_at_rows_par(0..active_row_num, 0..active_row_num);
     if let Err(errs) = res {
@@ -278,6 +342,36 @@ fn serial_test_super_circuit_1tx_1max_tx() {
     );
 }
 
+#[ignore]
+#[cfg(feature = "scroll")]
+#[test]
+fn serial_test_super_circuit_1tx_1max_tx_without_exp() {
+    let block = block_1tx_trace();
+    const MAX_TXS: usize = 1;
+    const MAX_CALLDATA: usize = 256;
+    const MAX_INNER_BLOCKS: usize = 1;
+    let circuits_params = CircuitsParams {
+        max_txs: MAX_TXS,
+        max_calldata: MAX_CALLDATA,
+        max_rws: 256,
+        max_copy_rows: 256,
+        max_exp_steps: 256,
+        max_bytecode: 512,
+        max_mpt_rows: 2049,
+        max_poseidon_rows: 512,
+        max_evm_rows: 0,
+        max_keccak_rows: 0,
+        max_inner_blocks: MAX_INNER_BLOCKS,
+        max_rlp_rows: 500,
+        ..Default::default()
+    };
+    test_super_circuit_with::<MAX_TXS, MAX_CALLDATA, MAX_INNER_BLOCKS, TEST_MOCK_RANDOMNESS, _>(
+        block,
+        circuits_params,
+        SuperCircuitWithoutExp,
+    );
+}
+
 #[ignore]
 #[cfg(feature = "scroll")]
 #[test]
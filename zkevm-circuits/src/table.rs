@@ -1159,6 +1159,9 @@ pub struct BytecodeTable {
     /// The RLC of the PUSH data (LE order), or 0.
     /// Warning: If the bytecode is truncated, this is the actual data, without zero-padding.
     pub push_rlc: Column<Advice>,
+    /// Keccak Code Hash, next to the poseidon code hash in `code_hash`
+    #[cfg(feature = "dual-codehash")]
+    pub keccak_code_hash: Column<Advice>,
 }
 
 impl BytecodeTable {
@@ -1175,6 +1178,8 @@ impl BytecodeTable {
             is_code,
             value,
             push_rlc,
+            #[cfg(feature = "dual-codehash")]
+            keccak_code_hash: meta.advice_column_in(SecondPhase),
         }
     }
 
@@ -1193,6 +1198,11 @@ impl BytecodeTable {
                 let bytecode_table_columns =
                     <BytecodeTable as LookupTable<F>>::advice_columns(self);
                 for bytecode in bytecodes.clone() {
+                    // the keccak code hash is the last column, copied along the bytecode rows
+                    #[cfg(feature = "dual-codehash")]
+                    let keccak_code_hash = Some(bytecode.keccak_hash_assignment(challenges));
+                    #[cfg(not(feature = "dual-codehash"))]
+                    let keccak_code_hash = None;
                     for row in bytecode.table_assignments(challenges) {
                         region.assign_fixed(
                             || format!("bytecode table row {offset}"),
@@ -1200,7 +1210,10 @@ impl BytecodeTable {
                             offset,
                             || Value::known(F::one()),
                         )?;
-                        for (&column, value) in bytecode_table_columns.iter().zip_eq(row) {
+                        for (&column, value) in bytecode_table_columns
+                            .iter()
+                            .zip_eq(row.into_iter().chain(keccak_code_hash))
+                        {
                             region.assign_advice(
                                 || format!("bytecode table row {offset}"),
                                 column,
@@ -1246,6 +1259,8 @@ impl<F: Field> LookupTable<F> for BytecodeTable {
             self.is_code.into(),
             self.value.into(),
             self.push_rlc.into(),
+            #[cfg(feature = "dual-codehash")]
+            self.keccak_code_hash.into(),
         ]
     }
 
@@ -1258,6 +1273,8 @@ impl<F: Field> LookupTable<F> for BytecodeTable {
             String::from("is_code"),
             String::from("value"),
             String::from("push_rlc"),
+            #[cfg(feature = "dual-codehash")]
+            String::from("keccak_code_hash"),
         ]
     }
 }
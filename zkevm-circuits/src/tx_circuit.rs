@@ -13,7 +13,10 @@ mod test;
 pub use dev::TxCircuitTester as TestTxCircuit;
 
 use crate::{
-    evm_circuit::util::constraint_builder::{BaseConstraintBuilder, ConstrainBuilderCommon},
+    evm_circuit::util::{
+        constraint_builder::{BaseConstraintBuilder, ConstrainBuilderCommon},
+        rlc,
+    },
     // sig_circuit::SigCircuit,
     table::{
         BlockContextFieldTag::{CumNumTxs, NumAllTxs, NumTxs},
@@ -796,11 +799,9 @@ impl<F: Field> SubCircuitConfig<F> for TxCircuitConfig<F> {
                 is_data_rlc(meta),
                 and::expr([
                     meta.query_advice(is_chain_id, Rotation::cur()),
-                    sum::expr([
-                        tx_type_bits.value_equals(Eip155, Rotation::cur())(meta),
-                        meta.query_advice(is_eip2930, Rotation::cur()),
-                        meta.query_advice(is_eip1559, Rotation::cur()),
-                    ]),
+                    tx_type_bits.value_equals_any([Eip155, Eip2930, Eip1559], Rotation::cur())(
+                        meta,
+                    ),
                 ]),
                 and::expr([
                     meta.query_advice(is_eip1559, Rotation::cur()),
@@ -2842,9 +2843,7 @@ impl<F: Field> TxCircuitConfig<F> {
         for (idx, byte) in tx.call_data.iter().enumerate() {
             let is_final = idx == (tx.call_data.len() - 1);
             gas_cost_acc += if *byte == 0 { 4 } else { 16 };
-            rlc = rlc
-                .zip(challenges.keccak_input())
-                .map(|(rlc, keccak_input)| rlc * keccak_input + F::from(*byte as u64));
+            rlc = rlc::accumulate(rlc, *byte, challenges.keccak_input());
             // the tx id of next row
             let tx_id_next = if !is_final {
                 tx.id
@@ -21,13 +21,15 @@ use crate::{
 use eth_types::{
     address,
     evm_types::gas_utils::{tx_access_list_gas_cost, tx_data_gas_cost},
-    word, H256, U256, U64,
+    word, AccessList, AccessListItem, H256, U256, U64,
 };
 use halo2_proofs::{
     dev::{MockProver, VerifyFailure},
     halo2curves::bn256::Fr,
 };
 use mock::{AddrOrWallet, MockTransaction};
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
 #[test]
 fn tx_circuit_unusable_rows() {
     assert_eq!(
@@ -160,6 +162,34 @@ fn build_eip1559_tx(id: usize) -> Transaction {
     tx
 }
 
+#[cfg(test)]
+fn build_eip2930_tx(id: u64) -> Transaction {
+    let mut rng = ChaCha20Rng::seed_from_u64(id);
+    let access_list = AccessList(vec![
+        AccessListItem {
+            address: address!("0x0000000000000000000000000000000000001111"),
+            storage_keys: vec![H256::from_low_u64_be(1), H256::from_low_u64_be(2)],
+        },
+        AccessListItem {
+            address: address!("0x0000000000000000000000000000000000002222"),
+            storage_keys: vec![],
+        },
+    ]);
+
+    MockTransaction::default()
+        .transaction_idx(id)
+        .from(AddrOrWallet::random(&mut rng))
+        .to(mock::MOCK_ACCOUNTS[0])
+        .nonce(word!("0x103"))
+        .value(word!("0x3e8"))
+        .gas_price(word!("0x4d2"))
+        .input(vec![1, 2, 3, 4, 5, 0, 6, 7, 8, 9].into())
+        .transaction_type(1)
+        .access_list(access_list)
+        .build()
+        .into()
+}
+
 fn run<F: Field>(
     txs: Vec<Transaction>,
     chain_id: u64,
@@ -221,6 +251,34 @@ fn tx_circuit_2tx_2max_tx_eip1559() {
     );
 }
 
+#[test]
+#[cfg(feature = "scroll")]
+fn tx_circuit_1tx_1max_eip2930() {
+    const MAX_TXS: usize = 1;
+    const MAX_CALLDATA: usize = 32;
+
+    let tx = build_eip2930_tx(1);
+    assert_eq!(tx.tx_type, TxType::Eip2930);
+
+    assert_eq!(
+        run::<Fr>(vec![tx], mock::MOCK_CHAIN_ID, MAX_TXS, MAX_CALLDATA, 0),
+        Ok(())
+    );
+}
+
+#[test]
+#[cfg(feature = "scroll")]
+fn tx_circuit_eip2930_bad_access_list() {
+    const MAX_TXS: usize = 1;
+    const MAX_CALLDATA: usize = 32;
+
+    // The access list rows of the tx table no longer match the signed RLP.
+    let mut tx = build_eip2930_tx(1);
+    tx.access_list.as_mut().unwrap().0[0].storage_keys[1] = H256::from_low_u64_be(3);
+
+    assert!(run::<Fr>(vec![tx], mock::MOCK_CHAIN_ID, MAX_TXS, MAX_CALLDATA, 0).is_err());
+}
+
 #[test]
 #[cfg(feature = "scroll")]
 fn tx_circuit_2tx_2max_tx() {
@@ -305,6 +363,30 @@ fn tx_circuit_l1_msg_tx() {
     );
 }
 
+#[test]
+#[cfg(feature = "scroll")]
+fn tx_circuit_l1_msg_and_l2_tx() {
+    const MAX_TXS: usize = 4;
+    const MAX_CALLDATA: usize = 420;
+
+    // An unsigned l1 msg followed by a signed l2 tx in the same block.
+    let l1_msg = build_l1_msg_tx();
+    let l2_tx = build_eip2930_tx(2);
+    assert!(l1_msg.tx_type.is_l1_msg());
+    assert!(!l2_tx.tx_type.is_l1_msg());
+
+    assert_eq!(
+        run::<Fr>(
+            vec![l1_msg, l2_tx],
+            mock::MOCK_CHAIN_ID,
+            MAX_TXS,
+            MAX_CALLDATA,
+            0
+        ),
+        Ok(())
+    );
+}
+
 #[test]
 #[cfg(feature = "scroll")]
 fn tx_circuit_bad_address() {
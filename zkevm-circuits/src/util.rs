@@ -17,6 +17,7 @@ use crate::{evm_circuit::util::rlc, table::TxLogFieldTag, witness};
 use eth_types::{Field, ToAddress, Word};
 pub use ethers_core::types::{Address, U256};
 pub use gadgets::util::Expr;
+use serde::Serialize;
 
 /// A wrapper of is_zero in gadgets which gives is_zero at any rotation
 pub mod is_zero;
@@ -38,11 +39,7 @@ pub(crate) fn random_linear_combine_word<F: Field>(bytes: [u8; 32], randomness:
 }
 
 pub(crate) fn rlc_be_bytes<F: Field>(bytes: &[u8], rand: Value<F>) -> Value<F> {
-    rand.map(|rand| {
-        bytes
-            .iter()
-            .fold(F::zero(), |acc, byte| acc * rand + F::from(*byte as u64))
-    })
+    rlc::value_be(bytes, rand)
 }
 
 /// All challenges used in `SuperCircuit`.
@@ -271,8 +268,25 @@ pub(crate) fn get_push_size(byte: u8) -> u64 {
     }
 }
 
-#[derive(Debug)]
-pub(crate) struct CircuitStats {
+/// Maximum degree of the polynomials of a gate.
+#[derive(Debug, Serialize)]
+pub struct GateDegree {
+    name: String,
+    degree: usize,
+}
+
+/// Maximum degrees of the input and table expressions of a lookup.
+#[derive(Debug, Serialize)]
+pub struct LookupDegree {
+    name: String,
+    input_degree: usize,
+    table_degree: usize,
+}
+
+/// Column, constraint and lookup counts of a configured circuit, see
+/// [`circuit_stats`].
+#[derive(Debug, Serialize)]
+pub struct CircuitStats {
     num_constraints: usize,
     num_fixed_columns: usize,
     num_lookups: usize,
@@ -289,9 +303,21 @@ pub(crate) struct CircuitStats {
     min_rotation: i32,
     max_rotation: i32,
     num_verification_ecmul: usize,
+    gates: Vec<GateDegree>,
+    lookups: Vec<LookupDegree>,
 }
 
-pub(crate) fn circuit_stats<F: Field>(meta: &ConstraintSystem<F>) -> CircuitStats {
+impl CircuitStats {
+    /// The stats as pretty printed JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("CircuitStats serialization can't fail")
+    }
+}
+
+/// Collects the [`CircuitStats`] of a configured constraint system, including
+/// the max degree of each gate and lookup, to find which ones drive the
+/// circuit degree and column count.
+pub fn circuit_stats<F: Field>(meta: &ConstraintSystem<F>) -> CircuitStats {
     let rotations = meta
         .advice_queries
         .iter()
@@ -324,9 +350,41 @@ pub(crate) fn circuit_stats<F: Field>(meta: &ConstraintSystem<F>) -> CircuitStat
             + meta.num_fixed_columns
             + 3 * meta.lookups.len()
             + rotations.len(),
+        gates: meta
+            .gates()
+            .iter()
+            .map(|gate| GateDegree {
+                name: gate.name().to_string(),
+                degree: max_degree(gate.polynomials()),
+            })
+            .collect(),
+        lookups: meta
+            .lookups
+            .iter()
+            .map(|lookup| LookupDegree {
+                name: lookup.name().to_string(),
+                input_degree: max_degree(lookup.input_expressions()),
+                table_degree: max_degree(lookup.table_expressions()),
+            })
+            .collect(),
     }
 }
 
+/// Returns the [`CircuitStats`] of the circuit `C` once configured.
+pub fn circuit_stats_of<F: Field, C: Circuit<F>>() -> CircuitStats {
+    let mut cs = ConstraintSystem::default();
+    C::configure(&mut cs);
+    circuit_stats(&cs)
+}
+
+fn max_degree<F: Field>(expressions: &[Expression<F>]) -> usize {
+    expressions
+        .iter()
+        .map(|expression| expression.degree())
+        .max()
+        .unwrap_or_default()
+}
+
 /// Returns number of unusable rows of the Circuit.
 /// The minimum unusable rows of a circuit is currently 6, where
 /// - 3 comes from minimum number of distinct queries to permutation argument witness column
@@ -44,7 +44,8 @@ pub struct Block<F> {
     pub end_block_last: ExecStep,
     /// Read write events in the RwTable
     pub rws: RwMap,
-    /// Bytecode used in the block
+    /// Bytecode used in the block, indexed by code hash so that accounts sharing the same code
+    /// share its rows in the bytecode circuit
     pub bytecodes: BTreeMap<Word, Bytecode>,
     /// The block context
     pub context: BlockContexts,
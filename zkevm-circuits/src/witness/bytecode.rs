@@ -55,7 +55,7 @@ impl Bytecode {
                 // Set the RLC result for all rows of the instruction, or 0.
                 let start = idx + 1;
                 let end = (start + push_data_left).min(self.bytes.len());
-                push_rlc = Self::make_push_rlc(challenges.evm_word(), &self.bytes[start..end]);
+                push_rlc = rlc::value_be(&self.bytes[start..end], challenges.evm_word());
             } else {
                 push_data_left -= 1;
             }
@@ -72,13 +72,13 @@ impl Bytecode {
         rows
     }
 
-    /// Return the RLC (LE order) of a bytecode slice.
-    fn make_push_rlc<F: Field>(rand: Value<F>, rows: &[u8]) -> Value<F> {
-        let mut acc = Value::known(F::zero());
-        for byte in rows {
-            acc = acc * rand + Value::known(F::from(*byte as u64));
-        }
-        acc
+    /// Assignment of the keccak code hash column, when `hash` is the poseidon code hash.
+    #[cfg(feature = "dual-codehash")]
+    pub fn keccak_hash_assignment<F: Field>(&self, challenges: &Challenges<Value<F>>) -> Value<F> {
+        let keccak_hash = crate::util::keccak(&self.bytes);
+        challenges
+            .evm_word()
+            .map(|challenge| rlc::value(&keccak_hash.to_le_bytes(), challenge))
     }
 
     /// get byte value and is_code pair
@@ -119,7 +119,7 @@ impl Bytecode {
 
             Some((start, length)) => {
                 let end = (start + length).min(self.bytes.len());
-                Self::make_push_rlc(challenges.evm_word(), &self.bytes[start..end])
+                rlc::value_be(&self.bytes[start..end], challenges.evm_word())
             }
         };
 
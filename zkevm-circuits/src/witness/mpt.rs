@@ -261,6 +261,15 @@ impl MptUpdates {
         wit_gen
     }
 
+    /// Build the updates of `rows` (account and storage rws, in any order) on top
+    /// of `init_trie`, generating the real state roots and mpt witness
+    pub fn from_rws(rows: &[Rw], init_trie: &ZktrieState) -> Self {
+        let init_root = U256::from_big_endian(init_trie.root());
+        let mut updates = Self::from_unsorted_rws_with_mock_state_roots(rows, init_root, init_root);
+        updates.fill_state_roots_from_generator(WitnessGenerator::from(init_trie));
+        updates
+    }
+
     pub(crate) fn mock_from(rows: &[Rw]) -> Self {
         Self::from_rws_with_mock_state_roots(rows, 0xcafeu64.into(), 0xdeadbeefu64.into())
     }
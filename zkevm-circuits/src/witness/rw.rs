@@ -9,7 +9,10 @@ use eth_types::{Address, Field, ToLittleEndian, ToScalar, Word, U256};
 
 use halo2_proofs::{circuit::Value, halo2curves::bn256::Fr};
 use itertools::Itertools;
-use rayon::prelude::{ParallelBridge, ParallelIterator};
+use rayon::{
+    prelude::{ParallelBridge, ParallelIterator},
+    slice::ParallelSliceMut,
+};
 
 use crate::{
     evm_circuit::util::rlc,
@@ -19,6 +22,9 @@ use crate::{
 
 use super::MptUpdates;
 
+mod artifact;
+mod dump;
+
 const ERR_MSG_FIRST: &str = "first access reads don't change value";
 const ERR_MSG_NON_FIRST: &str = "non-first access reads don't change value";
 
@@ -191,7 +197,9 @@ impl RwMap {
     /// Build Rws for assignment
     pub fn table_assignments(&self) -> Vec<Rw> {
         let mut rows = self.table_assignments_unsorted();
-        rows.sort_by_cached_key(Rw::as_key);
+        // The sort is stable, so rows sharing a key keep their rw_counter order no matter how
+        // the work is split across threads.
+        rows.par_sort_by_cached_key(Rw::as_key);
         rows
     }
 
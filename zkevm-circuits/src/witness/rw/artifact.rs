@@ -0,0 +1,467 @@
+//! Compact binary artifact of the [`RwMap`] witness.
+//!
+//! The artifact holds every rw of a block, so that the state circuit can be rebuilt and verified
+//! without rerunning the tracing of the block. All integers are little endian, words are stored
+//! as 32 big endian bytes and addresses as 20 bytes.
+
+use super::{Rw, RwMap};
+use crate::table::{
+    AccountFieldTag, CallContextFieldTag, RwTableTag, TxLogFieldTag, TxReceiptFieldTag,
+};
+use eth_types::{Address, Word};
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+use strum::IntoEnumIterator;
+
+const MAGIC: &[u8; 4] = b"RWMP";
+const VERSION: u8 = 1;
+
+fn invalid_data(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn decode_tag<T: IntoEnumIterator>(value: u8, discriminant: fn(&T) -> u8) -> io::Result<T> {
+    T::iter()
+        .find(|tag| discriminant(tag) == value)
+        .ok_or_else(|| invalid_data(format!("unknown {} {value}", std::any::type_name::<T>())))
+}
+
+struct Writer<W>(W);
+
+impl<W: Write> Writer<W> {
+    fn u8(&mut self, value: u8) -> io::Result<()> {
+        self.0.write_all(&[value])
+    }
+
+    fn bool(&mut self, value: bool) -> io::Result<()> {
+        self.u8(value as u8)
+    }
+
+    fn u64(&mut self, value: u64) -> io::Result<()> {
+        self.0.write_all(&value.to_le_bytes())
+    }
+
+    fn usize(&mut self, value: usize) -> io::Result<()> {
+        self.u64(value as u64)
+    }
+
+    fn address(&mut self, value: &Address) -> io::Result<()> {
+        self.0.write_all(value.as_bytes())
+    }
+
+    fn word(&mut self, value: &Word) -> io::Result<()> {
+        let mut bytes = [0u8; 32];
+        value.to_big_endian(&mut bytes);
+        self.0.write_all(&bytes)
+    }
+
+    fn rw(&mut self, rw: &Rw) -> io::Result<()> {
+        self.u8(rw.tag() as u8)?;
+        self.usize(rw.rw_counter())?;
+        match rw {
+            Rw::Start { .. } => Ok(()),
+            Rw::TxAccessListAccount {
+                is_write,
+                tx_id,
+                account_address,
+                is_warm,
+                is_warm_prev,
+                ..
+            } => {
+                self.bool(*is_write)?;
+                self.usize(*tx_id)?;
+                self.address(account_address)?;
+                self.bool(*is_warm)?;
+                self.bool(*is_warm_prev)
+            }
+            Rw::TxAccessListAccountStorage {
+                is_write,
+                tx_id,
+                account_address,
+                storage_key,
+                is_warm,
+                is_warm_prev,
+                ..
+            } => {
+                self.bool(*is_write)?;
+                self.usize(*tx_id)?;
+                self.address(account_address)?;
+                self.word(storage_key)?;
+                self.bool(*is_warm)?;
+                self.bool(*is_warm_prev)
+            }
+            Rw::TxRefund {
+                is_write,
+                tx_id,
+                value,
+                value_prev,
+                ..
+            } => {
+                self.bool(*is_write)?;
+                self.usize(*tx_id)?;
+                self.u64(*value)?;
+                self.u64(*value_prev)
+            }
+            Rw::Account {
+                is_write,
+                account_address,
+                field_tag,
+                value,
+                value_prev,
+                ..
+            } => {
+                self.bool(*is_write)?;
+                self.address(account_address)?;
+                self.u8(*field_tag as u8)?;
+                self.word(value)?;
+                self.word(value_prev)
+            }
+            Rw::AccountStorage {
+                is_write,
+                account_address,
+                storage_key,
+                value,
+                value_prev,
+                tx_id,
+                committed_value,
+                ..
+            } => {
+                self.bool(*is_write)?;
+                self.address(account_address)?;
+                self.word(storage_key)?;
+                self.word(value)?;
+                self.word(value_prev)?;
+                self.usize(*tx_id)?;
+                self.word(committed_value)
+            }
+            Rw::CallContext {
+                is_write,
+                call_id,
+                field_tag,
+                value,
+                ..
+            } => {
+                self.bool(*is_write)?;
+                self.usize(*call_id)?;
+                self.u8(*field_tag as u8)?;
+                self.word(value)
+            }
+            Rw::Stack {
+                is_write,
+                call_id,
+                stack_pointer,
+                value,
+                ..
+            } => {
+                self.bool(*is_write)?;
+                self.usize(*call_id)?;
+                self.usize(*stack_pointer)?;
+                self.word(value)
+            }
+            Rw::Memory {
+                is_write,
+                call_id,
+                memory_address,
+                value,
+                value_prev,
+                ..
+            } => {
+                self.bool(*is_write)?;
+                self.usize(*call_id)?;
+                self.u64(*memory_address)?;
+                self.word(value)?;
+                self.word(value_prev)
+            }
+            Rw::TxLog {
+                is_write,
+                tx_id,
+                log_id,
+                field_tag,
+                index,
+                value,
+                ..
+            } => {
+                self.bool(*is_write)?;
+                self.usize(*tx_id)?;
+                self.u64(*log_id)?;
+                self.u8(*field_tag as u8)?;
+                self.usize(*index)?;
+                self.word(value)
+            }
+            Rw::TxReceipt {
+                is_write,
+                tx_id,
+                field_tag,
+                value,
+                ..
+            } => {
+                self.bool(*is_write)?;
+                self.usize(*tx_id)?;
+                self.u8(*field_tag as u8)?;
+                self.u64(*value)
+            }
+        }
+    }
+}
+
+struct Reader<R>(R);
+
+impl<R: Read> Reader<R> {
+    fn bytes<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        let mut bytes = [0u8; N];
+        self.0.read_exact(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> io::Result<u8> {
+        Ok(self.bytes::<1>()?[0])
+    }
+
+    fn bool(&mut self) -> io::Result<bool> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(invalid_data(format!("invalid bool {value}"))),
+        }
+    }
+
+    fn u64(&mut self) -> io::Result<u64> {
+        Ok(u64::from_le_bytes(self.bytes()?))
+    }
+
+    fn usize(&mut self) -> io::Result<usize> {
+        Ok(self.u64()? as usize)
+    }
+
+    fn address(&mut self) -> io::Result<Address> {
+        Ok(Address::from(self.bytes::<20>()?))
+    }
+
+    fn word(&mut self) -> io::Result<Word> {
+        Ok(Word::from_big_endian(&self.bytes::<32>()?))
+    }
+
+    fn rw(&mut self) -> io::Result<Rw> {
+        let tag = decode_tag(self.u8()?, |tag: &RwTableTag| *tag as u8)?;
+        let rw_counter = self.usize()?;
+        Ok(match tag {
+            RwTableTag::Start => Rw::Start { rw_counter },
+            RwTableTag::TxAccessListAccount => Rw::TxAccessListAccount {
+                rw_counter,
+                is_write: self.bool()?,
+                tx_id: self.usize()?,
+                account_address: self.address()?,
+                is_warm: self.bool()?,
+                is_warm_prev: self.bool()?,
+            },
+            RwTableTag::TxAccessListAccountStorage => Rw::TxAccessListAccountStorage {
+                rw_counter,
+                is_write: self.bool()?,
+                tx_id: self.usize()?,
+                account_address: self.address()?,
+                storage_key: self.word()?,
+                is_warm: self.bool()?,
+                is_warm_prev: self.bool()?,
+            },
+            RwTableTag::TxRefund => Rw::TxRefund {
+                rw_counter,
+                is_write: self.bool()?,
+                tx_id: self.usize()?,
+                value: self.u64()?,
+                value_prev: self.u64()?,
+            },
+            RwTableTag::Account => Rw::Account {
+                rw_counter,
+                is_write: self.bool()?,
+                account_address: self.address()?,
+                field_tag: decode_tag(self.u8()?, |tag: &AccountFieldTag| *tag as u8)?,
+                value: self.word()?,
+                value_prev: self.word()?,
+            },
+            RwTableTag::AccountStorage => Rw::AccountStorage {
+                rw_counter,
+                is_write: self.bool()?,
+                account_address: self.address()?,
+                storage_key: self.word()?,
+                value: self.word()?,
+                value_prev: self.word()?,
+                tx_id: self.usize()?,
+                committed_value: self.word()?,
+            },
+            RwTableTag::CallContext => Rw::CallContext {
+                rw_counter,
+                is_write: self.bool()?,
+                call_id: self.usize()?,
+                field_tag: decode_tag(self.u8()?, |tag: &CallContextFieldTag| *tag as u8)?,
+                value: self.word()?,
+            },
+            RwTableTag::Stack => Rw::Stack {
+                rw_counter,
+                is_write: self.bool()?,
+                call_id: self.usize()?,
+                stack_pointer: self.usize()?,
+                value: self.word()?,
+            },
+            RwTableTag::Memory => Rw::Memory {
+                rw_counter,
+                is_write: self.bool()?,
+                call_id: self.usize()?,
+                memory_address: self.u64()?,
+                value: self.word()?,
+                value_prev: self.word()?,
+            },
+            RwTableTag::TxLog => Rw::TxLog {
+                rw_counter,
+                is_write: self.bool()?,
+                tx_id: self.usize()?,
+                log_id: self.u64()?,
+                field_tag: decode_tag(self.u8()?, |tag: &TxLogFieldTag| *tag as u8)?,
+                index: self.usize()?,
+                value: self.word()?,
+            },
+            RwTableTag::TxReceipt => Rw::TxReceipt {
+                rw_counter,
+                is_write: self.bool()?,
+                tx_id: self.usize()?,
+                field_tag: decode_tag(self.u8()?, |tag: &TxReceiptFieldTag| *tag as u8)?,
+                value: self.u64()?,
+            },
+        })
+    }
+}
+
+impl RwMap {
+    /// Serialize all the rws into the binary artifact format.
+    pub fn write_artifact<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut writer = Writer(writer);
+        writer.0.write_all(MAGIC)?;
+        writer.u8(VERSION)?;
+        // Iterate the tags in a fixed order so that the artifact is deterministic.
+        for tag in RwTableTag::iter() {
+            let rws = self.0.get(&tag).map(Vec::as_slice).unwrap_or_default();
+            writer.usize(rws.len())?;
+            for rw in rws {
+                debug_assert_eq!(rw.tag(), tag);
+                writer.rw(rw)?;
+            }
+        }
+        writer.0.flush()
+    }
+
+    /// Deserialize the rws from an artifact written by [`Self::write_artifact`].
+    pub fn read_artifact<R: Read>(reader: R) -> io::Result<Self> {
+        let mut reader = Reader(reader);
+        if &reader.bytes::<4>()? != MAGIC {
+            return Err(invalid_data("not a rw map artifact".to_string()));
+        }
+        let version = reader.u8()?;
+        if version != VERSION {
+            return Err(invalid_data(format!(
+                "unsupported rw map artifact version {version}"
+            )));
+        }
+        let mut rws = HashMap::new();
+        for tag in RwTableTag::iter() {
+            let len = reader.usize()?;
+            let entries = (0..len)
+                .map(|_| {
+                    let rw = reader.rw()?;
+                    if rw.tag() != tag {
+                        return Err(invalid_data(format!(
+                            "{:?} rw found in {tag:?} section",
+                            rw.tag()
+                        )));
+                    }
+                    Ok(rw)
+                })
+                .collect::<io::Result<Vec<_>>>()?;
+            if !entries.is_empty() {
+                rws.insert(tag, entries);
+            }
+        }
+        Ok(Self(rws))
+    }
+
+    /// Write the artifact into a file.
+    pub fn write_artifact_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.write_artifact(BufWriter::new(File::create(path)?))
+    }
+
+    /// Read the artifact from a file.
+    pub fn read_artifact_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::read_artifact(BufReader::new(File::open(path)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rws() -> Vec<Rw> {
+        vec![
+            Rw::Stack {
+                rw_counter: 1,
+                is_write: true,
+                call_id: 1,
+                stack_pointer: 1023,
+                value: Word::from(0xcafe),
+            },
+            Rw::AccountStorage {
+                rw_counter: 2,
+                is_write: true,
+                account_address: Address::repeat_byte(0xab),
+                storage_key: Word::MAX,
+                value: Word::from(3),
+                value_prev: Word::zero(),
+                tx_id: 1,
+                committed_value: Word::zero(),
+            },
+            Rw::CallContext {
+                rw_counter: 3,
+                is_write: false,
+                call_id: 1,
+                field_tag: CallContextFieldTag::CallerAddress,
+                value: Word::from(0xab),
+            },
+            Rw::TxReceipt {
+                rw_counter: 4,
+                is_write: true,
+                tx_id: 1,
+                field_tag: TxReceiptFieldTag::CumulativeGasUsed,
+                value: 21000,
+            },
+        ]
+    }
+
+    #[test]
+    fn artifact_roundtrip() {
+        let mut rw_map = RwMap::default();
+        for rw in rws() {
+            rw_map.0.entry(rw.tag()).or_insert_with(Vec::new).push(rw);
+        }
+
+        let mut bytes = vec![];
+        rw_map.write_artifact(&mut bytes).unwrap();
+        let decoded = RwMap::read_artifact(bytes.as_slice()).unwrap();
+
+        assert_eq!(decoded.table_assignments(), rw_map.table_assignments());
+    }
+
+    #[test]
+    fn artifact_rejects_truncated_input() {
+        let mut rw_map = RwMap::default();
+        for rw in rws() {
+            rw_map.0.entry(rw.tag()).or_insert_with(Vec::new).push(rw);
+        }
+
+        let mut bytes = vec![];
+        rw_map.write_artifact(&mut bytes).unwrap();
+        bytes.truncate(bytes.len() - 1);
+
+        assert!(RwMap::read_artifact(bytes.as_slice()).is_err());
+    }
+}
@@ -0,0 +1,188 @@
+//! Human readable dump of the [`RwMap`] witness, and a diff of two of them, to
+//! track down state circuit mismatches.
+
+use super::{Rw, RwMap};
+use eth_types::Word;
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+const CSV_HEADER: &str =
+    "rw_counter,is_write,tag,id,address,field_tag,storage_key,value,value_prev";
+
+fn opt<T: Display>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+impl Rw {
+    fn value_prev_word(&self) -> Option<Word> {
+        match self {
+            Self::Account { value_prev, .. }
+            | Self::AccountStorage { value_prev, .. }
+            | Self::Memory { value_prev, .. } => Some(*value_prev),
+            Self::TxAccessListAccount { is_warm_prev, .. }
+            | Self::TxAccessListAccountStorage { is_warm_prev, .. } => {
+                Some(Word::from(*is_warm_prev as u64))
+            }
+            Self::TxRefund { value_prev, .. } => Some(Word::from(*value_prev)),
+            Self::Start { .. }
+            | Self::Stack { .. }
+            | Self::CallContext { .. }
+            | Self::TxLog { .. }
+            | Self::TxReceipt { .. } => None,
+        }
+    }
+
+    fn csv_row(&self) -> String {
+        format!(
+            "{},{},{:?},{},{},{},{},{:#x},{}",
+            self.rw_counter(),
+            self.is_write() as u8,
+            self.tag(),
+            opt(self.id()),
+            opt(self.address().map(|a| format!("{a:?}"))),
+            opt(self.field_tag()),
+            opt(self.storage_key().map(|k| format!("{k:#x}"))),
+            self.value_word(),
+            opt(self.value_prev_word().map(|v| format!("{v:#x}"))),
+        )
+    }
+}
+
+/// Sort key of a rw: its rw counter, then its tag and its position among the
+/// rws of that tag sharing the rw counter. Padding rows share rw counters, so
+/// the rw counter alone would collapse them.
+type RwKey = (usize, usize, usize);
+
+impl RwMap {
+    fn by_rw_counter(&self) -> BTreeMap<RwKey, &Rw> {
+        let mut rws = BTreeMap::new();
+        for rows in self.0.values() {
+            let mut last = None;
+            let mut position = 0;
+            for rw in rows {
+                let rw_counter = rw.rw_counter();
+                position = if last == Some(rw_counter) {
+                    position + 1
+                } else {
+                    0
+                };
+                last = Some(rw_counter);
+                rws.insert((rw_counter, rw.tag() as usize, position), rw);
+            }
+        }
+        rws
+    }
+
+    /// Write every rw as a CSV row, ordered by rw counter. Words are written in hex.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "{CSV_HEADER}")?;
+        for rw in self.by_rw_counter().values() {
+            writeln!(writer, "{}", rw.csv_row())?;
+        }
+        writer.flush()
+    }
+
+    /// Write the CSV dump of [`Self::write_csv`] to the file at `path`.
+    pub fn write_csv_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.write_csv(BufWriter::new(File::create(path)?))
+    }
+
+    /// Compare two rw maps rw counter by rw counter, returning the pairs which
+    /// differ (`None` when a rw is only on one side), ordered by rw counter.
+    pub fn diff(&self, other: &Self) -> Vec<(Option<Rw>, Option<Rw>)> {
+        let mut lhs = self.by_rw_counter();
+        let mut rhs = other.by_rw_counter();
+        let mut keys: Vec<RwKey> = lhs.keys().chain(rhs.keys()).copied().collect();
+        keys.sort_unstable();
+        keys.dedup();
+
+        keys.into_iter()
+            .filter_map(|key| {
+                let pair = (lhs.remove(&key).copied(), rhs.remove(&key).copied());
+                (pair.0 != pair.1).then_some(pair)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eth_types::Address;
+
+    fn rw_map(rws: &[Rw]) -> RwMap {
+        let mut rw_map = RwMap::default();
+        for rw in rws {
+            rw_map.0.entry(rw.tag()).or_insert_with(Vec::new).push(*rw);
+        }
+        rw_map
+    }
+
+    #[test]
+    fn csv_and_diff() {
+        let stack = Rw::Stack {
+            rw_counter: 1,
+            is_write: true,
+            call_id: 1,
+            stack_pointer: 1023,
+            value: Word::from(0xcafe),
+        };
+        let storage = Rw::AccountStorage {
+            rw_counter: 2,
+            is_write: true,
+            account_address: Address::repeat_byte(0xab),
+            storage_key: Word::one(),
+            value: Word::from(3),
+            value_prev: Word::zero(),
+            tx_id: 1,
+            committed_value: Word::zero(),
+        };
+        let lhs = rw_map(&[stack, storage]);
+
+        let mut csv = Vec::new();
+        lhs.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert_eq!(csv.lines().count(), 3);
+        assert!(csv.lines().nth(1).unwrap().starts_with("1,1,Stack,1,"));
+
+        assert!(lhs.diff(&lhs).is_empty());
+        let rhs = rw_map(&[Rw::Stack {
+            value: Word::from(0xbeef),
+            ..stack
+        }]);
+        assert_eq!(
+            lhs.diff(&rhs),
+            vec![
+                (
+                    Some(stack),
+                    Some(Rw::Stack {
+                        value: Word::from(0xbeef),
+                        ..stack
+                    })
+                ),
+                (Some(storage), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn padding_rows_are_kept() {
+        let padding = [Rw::Start { rw_counter: 0 }; 3];
+        let lhs = rw_map(&padding);
+
+        let mut csv = Vec::new();
+        lhs.write_csv(&mut csv).unwrap();
+        assert_eq!(String::from_utf8(csv).unwrap().lines().count(), 4);
+
+        let rhs = rw_map(&padding[..1]);
+        assert_eq!(
+            lhs.diff(&rhs),
+            vec![(Some(padding[0]), None), (Some(padding[0]), None)]
+        );
+    }
+}
@@ -17,7 +17,7 @@ use crate::{
 };
 use bus_mapping::circuit_input_builder::{self, get_dummy_tx_hash, TxL1Fee};
 use eth_types::{
-    evm_types::gas_utils::{tx_access_list_gas_cost, tx_data_gas_cost},
+    evm_types::gas_utils::{tx_access_list_gas_cost, tx_data_gas_cost, tx_intrinsic_gas},
     geth_types::{access_list_size, TxType, TxType::PreEip155},
     sign_types::{
         biguint_to_32bytes_le, ct_option_ok_or, get_dummy_tx, recover_pk2, SignData, SECP256K1_Q,
@@ -26,7 +26,7 @@ use eth_types::{
     Word, H256,
 };
 use ethers_core::{
-    types::TransactionRequest,
+    types::transaction::eip2718::TypedTransaction,
     utils::{keccak256, rlp::Encodable},
 };
 use halo2_proofs::{
@@ -157,6 +157,16 @@ impl Transaction {
         })
     }
 
+    /// Intrinsic gas of this tx, which is charged before its execution begins.
+    pub fn intrinsic_gas_cost(&self) -> u64 {
+        tx_intrinsic_gas(
+            self.is_create,
+            self.call_data_length,
+            self.call_data_gas_cost,
+            self.access_list_gas_cost,
+        )
+    }
+
     /// Assignments for tx table, split into tx_data (all fields except
     /// calldata) and tx_calldata
 
@@ -1110,7 +1120,7 @@ impl<F: Field> RlpFsmWitnessGen<F> for Transaction {
                     (Value::known(F::zero()), Value::known(F::zero())),
                     |(rlc, gas_cost_acc), (i, &byte_value)| {
                         let byte_cost = if byte_value == 0 { 4 } else { 16 };
-                        *rlc = *rlc * r + Value::known(F::from(byte_value as u64));
+                        *rlc = rlc::accumulate(*rlc, byte_value, r);
                         *gas_cost_acc = *gas_cost_acc + Value::known(F::from(byte_cost));
                         Some(DataTable {
                             tx_id,
@@ -1144,32 +1154,20 @@ impl From<MockTransaction> for Transaction {
             s: mock_tx.s.expect("tx expected to be signed"),
             v: mock_tx.v.expect("tx expected to be signed").as_u64(),
         };
-        let (rlp_unsigned, rlp_signed) = {
-            let mut legacy_tx = TransactionRequest::new()
-                .from(mock_tx.from.address())
-                .nonce(mock_tx.nonce)
-                .gas(mock_tx.gas)
-                .value(mock_tx.value)
-                .data(mock_tx.input.clone())
-                .chain_id(mock_tx.chain_id);
-            if let Some(gas_price) = mock_tx.gas_price {
-                legacy_tx = legacy_tx.gas_price(gas_price);
-            }
-            if !is_create {
-                legacy_tx = legacy_tx.to(mock_tx.to.as_ref().map(|to| to.address()).unwrap());
-            }
-
-            let unsigned = legacy_tx.rlp().to_vec();
-            let signed = legacy_tx.rlp_signed(&sig).to_vec();
-
-            (unsigned, signed)
+        let typed_tx = mock_tx.typed_tx();
+        let rlp_unsigned = typed_tx.rlp().to_vec();
+        let rlp_signed = typed_tx.rlp_signed(&sig).to_vec();
+        let tx_type = match typed_tx {
+            TypedTransaction::Legacy(_) => TxType::Eip155,
+            TypedTransaction::Eip2930(_) => TxType::Eip2930,
+            TypedTransaction::Eip1559(_) => TxType::Eip1559,
         };
         let access_list = Some(mock_tx.access_list);
         Self {
             block_number: 1,
             id: mock_tx.transaction_index.as_usize(),
             hash: mock_tx.hash.unwrap_or_default(),
-            tx_type: TxType::Eip155,
+            tx_type,
             nonce: mock_tx.nonce.as_u64(),
             gas: mock_tx.gas.as_u64(),
             gas_price: mock_tx.gas_price.unwrap_or_default(),
@@ -1,5 +1,5 @@
 //! Represent the storage state under zktrie as implement
-use eth_types::{Address, Hash, Word};
+use eth_types::{Address, Bytes, EIP1186ProofResponse, Hash, Word};
 use mpt_circuits::MPTProofType;
 
 use std::{collections::HashSet, io::Error};
@@ -170,6 +170,39 @@ impl ZktrieState {
 
         Ok(state)
     }
+
+    /// construct from `eth_getProof` responses of the touched accounts and
+    /// slots, as returned by a zktrie (l2geth) node at the parent block
+    ///
+    /// Only zktrie proofs are supported: the proofs of an MPT node (e.g. an L1
+    /// geth) are rlp encoded trie nodes, which fail to parse as zktrie nodes.
+    pub fn from_zktrie_eth_proofs(
+        state_root: Hash,
+        proofs: &[EIP1186ProofResponse],
+    ) -> Result<Self, Error> {
+        let account_proofs = proofs.iter().map(|proof| {
+            (
+                &proof.address,
+                proof.account_proof.iter().map(Bytes::as_ref),
+            )
+        });
+        let storage_proofs = proofs.iter().flat_map(|proof| {
+            proof.storage_proof.iter().map(move |storage| {
+                (
+                    &proof.address,
+                    &storage.key,
+                    storage.proof.iter().map(Bytes::as_ref),
+                )
+            })
+        });
+
+        Self::from_trace_with_additional(
+            state_root,
+            account_proofs,
+            storage_proofs,
+            std::iter::empty(),
+        )
+    }
 }
 
 #[cfg(any(feature = "test", test))]
@@ -1,5 +1,5 @@
 use super::*;
-use eth_types::{Bytes, Word};
+use eth_types::{Bytes, StorageProof, Word};
 use log::info;
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -182,6 +182,38 @@ fn deserialize_example() {
     }
 }
 
+#[test]
+fn from_zktrie_eth_proofs() {
+    let trace: StorageTrace = serde_json::from_str(EXAMPLE_TRACE).unwrap();
+    let proofs = trace
+        .proofs
+        .unwrap()
+        .into_iter()
+        .map(|(address, account_proof)| EIP1186ProofResponse {
+            address,
+            account_proof,
+            storage_proof: trace
+                .storage_proofs
+                .get(&address)
+                .into_iter()
+                .flatten()
+                .map(|(key, proof)| StorageProof {
+                    key: *key,
+                    proof: proof.clone(),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+
+    let state = ZktrieState::from_zktrie_eth_proofs(trace.root_before, &proofs).unwrap();
+    let (expected, _, _) = build_state_from_string(EXAMPLE_TRACE);
+    assert_eq!(state.root(), expected.root());
+    assert_eq!(state.addr_cache, expected.addr_cache);
+    assert_eq!(state.storage_cache, expected.storage_cache);
+}
+
 #[test]
 fn witgen_init_writer() {
     use witness::WitnessGenerator;